@@ -0,0 +1,40 @@
+// Generates the day registry from the `src/day_NN` directories, so that adding a day is just a
+// matter of creating its module.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut days: Vec<(u8, String)> = fs::read_dir(&src)
+        .expect("src should be readable")
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let num = name.strip_prefix("day_")?;
+            (num.len() == 2).then_some(())?;
+            Some((num.parse().ok()?, name))
+        })
+        .filter(|(_, name)| src.join(name).join("mod.rs").is_file())
+        .collect();
+    days.sort_unstable();
+
+    let mut out = String::new();
+    for (_, name) in &days {
+        let path = src.join(name).join("mod.rs");
+        writeln!(
+            out,
+            "#[path = {:?}]\npub mod {name};",
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+    writeln!(out, "\npub const DAYS: &[Day] = &[").unwrap();
+    for (day, name) in &days {
+        writeln!(out, "    Day::new::<{name}::Day{day}>(),").unwrap();
+    }
+    writeln!(out, "];").unwrap();
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set by cargo");
+    fs::write(Path::new(&out_dir).join("days.rs"), out).expect("days.rs should be writable");
+}
@@ -1,67 +1,742 @@
-use std::time::Instant;
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use advent_of_code_2024::*;
+use advent_of_code_2024::{
+    client::submit,
+    input::Source,
+    runner::{
+        baseline::{self, Baseline},
+        cpu,
+        output::{self, Format},
+        verify::{self, Expected, Verdict},
+        Report,
+    },
+    *,
+};
 
-#[cfg(feature = "io")]
-macro_rules! input_str {
-    ($d:expr) => {
-        std::fs::read_to_string(concat!("input/2024/day", $d, ".txt")).unwrap()
-    };
+fn print_report(args: &Args, expected: Option<&Expected>, report: &Report) {
+    let bench = args.bench.is_some();
+    for (step, stats) in Step::ALL.iter().zip(&report.timings) {
+        let Some(stats) = stats else {
+            continue;
+        };
+        let answer = match step {
+            Step::Parse if args.skip_parse_timing => continue,
+            Step::Parse => None,
+            Step::Part1 => report.answers[0].as_ref(),
+            Step::Part2 => report.answers[1].as_ref(),
+        };
+
+        let mut line = match bench {
+            true => format!(
+                "day {}-{step} (median {:7.1?}, min {:7.1?}, mean {:7.1?}, stddev {:7.1?}, n={})",
+                report.day, stats.median, stats.min, stats.mean, stats.stddev, stats.samples
+            ),
+            false => match step {
+                Step::Parse => format!("day {}-{step} ({:.1?})", report.day, stats.median),
+                _ => format!("day {}-{step} ({:7.1?})", report.day, stats.median),
+            },
+        };
+        if let Some(answer) = answer {
+            line += &format!(": {answer}");
+            if let (Some(expected), Some(part)) = (expected, step.part()) {
+                line += &format!(" {}", expected.check(report.day, part, answer));
+            }
+        }
+        println!("{line}");
+    }
+    println!();
 }
 
-#[cfg(not(feature = "io"))]
-macro_rules! input_str {
-    ($d:expr) => {
-        include_str!(concat!("../../input/2024/day", $d, ".txt"))
+enum Outcome {
+    Skipped(String),
+    Failed(String),
+    Ran(Box<Report>),
+}
+
+fn run_day(args: &Args, day: &Day) -> Outcome {
+    let (source, explicit) = args.input_source(day.day);
+    let input = match source.read() {
+        // `new-day` leaves an empty placeholder, which `fetch` treats as missing too
+        Ok(input) if input.is_empty() && !explicit => {
+            return Outcome::Skipped(format!(
+                "warning: skipping day {}, the input at {source} is empty\n",
+                day.day
+            ));
+        }
+        Ok(input) => input,
+        Err(e) if e.kind() == ErrorKind::NotFound && !explicit => {
+            return Outcome::Skipped(format!(
+                "warning: skipping day {}, no input at {source}\n",
+                day.day
+            ));
+        }
+        Err(e) => {
+            return Outcome::Failed(format!(
+                "day {}: failed to read input from {source}: {e}",
+                day.day
+            ));
+        }
     };
+
+    let config = runner::Config {
+        parts: [args.runs_part(1), args.runs_part(2)],
+        warmup: args
+            .warmup
+            .unwrap_or(if args.bench.is_some() { 3 } else { 0 }),
+        samples: args.bench.unwrap_or(1),
+    };
+    match runner::run(day, &input, &config) {
+        Ok(report) => Outcome::Ran(Box::new(report)),
+        Err(e) => Outcome::Failed(format!("day {}: failed to parse input: {e}\n", day.day)),
+    }
+}
+
+/// Runs `days` on up to `jobs` threads, passing each outcome to `handle` in the same order as
+/// `days`. Returns the CPU time used by all the threads, if it can be measured.
+fn run_days(
+    args: &Args,
+    days: &[&Day],
+    jobs: usize,
+    mut handle: impl FnMut(Outcome),
+) -> Option<Duration> {
+    let next = &AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            scope.spawn(move || {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let start = cpu::thread_cpu_time();
+                    let outcome = run_day(args, day);
+                    let cpu =
+                        Option::zip(cpu::thread_cpu_time(), start).map(|(end, start)| end - start);
+                    if tx.send((day.day, outcome, cpu)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        // results can arrive out of order, so hold on to them until it's their turn
+        let mut pending = BTreeMap::new();
+        let mut order = days.iter().map(|d| d.day).peekable();
+        let mut total = Some(Duration::ZERO);
+        for (day, outcome, cpu) in rx {
+            total = Option::zip(total, cpu).map(|(total, cpu)| total + cpu);
+            pending.insert(day, outcome);
+            while let Some(outcome) = order.peek().and_then(|day| pending.remove(day)) {
+                handle(outcome);
+                order.next();
+            }
+        }
+        total
+    })
 }
 
-macro_rules! run_parts {
-    ($m:ident, $d:expr$(, $g:expr)?) => {
-        let mut instant = Instant::now();
-        let input = input_str!($d);
-        $(
-            let input = $g(&input);
-            println!("day {}-parse ({:.1?})", $d, instant.elapsed());
-            instant = Instant::now();
-        )?
-        let part1 = $m::part_1(&input);
-        println!("day {}-1 ({:7.1?}): {}", $d, instant.elapsed(), part1);
-        instant = Instant::now();
-        let part2 = $m::part_2(&input);
-        println!("day {}-2 ({:7.1?}): {}", $d, instant.elapsed(), part2);
-        println!();
+const USAGE: &str = "\
+usage: bin [options]
+       bin new-day <N> [--input-dir <DIR>]
+       bin fetch <N|A..B|A..=B> [--input-dir <DIR>]
+       bin submit --day <N> --part <1|2> [--answer <ANSWER>] [--history <FILE>] [options]
+
+fetch downloads any inputs that aren't already in the input directory, from $AOC_BASE_URL
+(default: https://adventofcode.com) using the session token in $AOC_SESSION or the file
+named by $AOC_SESSION_FILE (default: cookies).
+
+submit posts the answer (computed from the input unless --answer is given) to the same server
+and records it in the history file (default: submissions.tsv). Answers that the history shows
+are wrong, or out of the known too-high/too-low bounds, aren't submitted again.
+
+--variant picks an alternative implementation of the selected days that have one, e.g. 'corners'
+for day 12, which counts the sides of each region by its corners rather than by its edges.
+
+options:
+    --day <N|A..B|A..=B>    only run the given day(s), may be repeated
+    --part <1|2>            only run the given part
+    --skip-parse-timing     don't report the time taken to parse the input
+    --input-dir <DIR>       read day{N}.txt inputs from DIR (default: $AOC_INPUT_DIR or input/2024)
+    --input <[DAY=]FILE>    read the input for DAY from FILE ('-' for stdin), may be repeated;
+                            DAY may be omitted when only a single day is selected
+    --bench <N>             time each step N times and report min/median/mean/stddev
+    --warmup <N>            untimed runs of each step before timing (default: 3 with --bench)
+    --bench-output <FILE>   append --bench results to FILE (default: bench-results.tsv)
+    --format <FORMAT>       output format: text, json or csv (default: text)
+    --save-baseline         save the timings of this run as the baseline (merged with the existing one)
+    --compare-baseline      flag steps that are slower than the baseline, failing if any are
+    --baseline <FILE>       baseline timings file (default: baseline.tsv)
+    --threshold <PCT>       how much slower than the baseline is a regression (default: 10)
+    --verify                check answers against the known answers, failing on a mismatch
+    --answers <FILE>        known answers for --verify (default: answers.txt)
+    -j, --jobs <N>          run up to N days in parallel (default: 1), not with --bench or
+                            the baseline options as the days would compete for cores
+    --visualize <STYLE>     draw how the selected --day (and --part) is solved instead of running
+                            it, as an animation in the terminal (animate) or plain text (frames)
+    --variant <NAME>        run the NAME variant of the selected days that have one
+    -h, --help              print this message";
+
+#[derive(Default)]
+struct Args {
+    days: Option<Vec<u8>>,
+    part: Option<u8>,
+    skip_parse_timing: bool,
+    input_dir: Option<PathBuf>,
+    inputs: Vec<(u8, Source)>,
+    bench: Option<usize>,
+    warmup: Option<usize>,
+    bench_output: Option<PathBuf>,
+    format: Format,
+    verify: bool,
+    answers: Option<PathBuf>,
+    save_baseline: bool,
+    compare_baseline: bool,
+    baseline: Option<PathBuf>,
+    threshold: Option<f64>,
+    jobs: Option<usize>,
+    visualize: Option<Visualization>,
+    variant: Option<String>,
+}
+
+impl Args {
+    fn runs_day(&self, day: u8) -> bool {
+        self.days.as_ref().is_none_or(|days| days.contains(&day))
+    }
+
+    /// `day`, or its `--variant` if it has one.
+    fn resolve(&self, day: &'static Day) -> &'static Day {
+        self.variant
+            .as_deref()
+            .and_then(|name| day.variant(name))
+            .unwrap_or(day)
+    }
+
+    fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Where to read the input for `day` from, and whether that was explicitly requested.
+    fn input_source(&self, day: u8) -> (Source, bool) {
+        match self.inputs.iter().find(|(d, _)| *d == day) {
+            Some((_, source)) => (source.clone(), true),
+            None => {
+                let dir = input::input_dir(self.input_dir.as_deref());
+                (Source::File(input::day_path(&dir, day)), false)
+            }
+        }
+    }
+}
+
+fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let num = |n: &str| {
+        n.trim()
+            .parse::<u8>()
+            .map_err(|_| format!("invalid day '{n}'"))
     };
+
+    let days = if let Some((start, end)) = s.split_once("..=") {
+        (num(start)?..=num(end)?).collect()
+    } else if let Some((start, end)) = s.split_once("..") {
+        (num(start)?..num(end)?).collect()
+    } else {
+        vec![num(s)?]
+    };
+
+    match days.is_empty() {
+        true => Err(format!("day range '{s}' is empty")),
+        false => Ok(days),
+    }
+}
+
+fn parse_count(s: &str) -> Result<usize, String> {
+    s.parse()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or(format!("invalid count '{s}', expected a positive integer"))
+}
+
+fn parse_non_negative(s: &str) -> Result<usize, String> {
+    s.parse()
+        .map_err(|_| format!("invalid count '{s}', expected a non-negative integer"))
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args::default();
+    let mut inputs = vec![];
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for '{arg}'"));
+        match arg.as_str() {
+            "--day" => {
+                let days = parse_days(&value()?)?;
+                parsed.days.get_or_insert_with(Vec::new).extend(days);
+            }
+            "--part" => {
+                parsed.part = match value()?.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    p => return Err(format!("invalid part '{p}', expected 1 or 2")),
+                }
+            }
+            "--skip-parse-timing" => parsed.skip_parse_timing = true,
+            "--input-dir" => parsed.input_dir = Some(value()?.into()),
+            "--input" => inputs.push(value()?),
+            "--bench" => parsed.bench = Some(parse_count(&value()?)?),
+            "--warmup" => parsed.warmup = Some(parse_non_negative(&value()?)?),
+            "--bench-output" => parsed.bench_output = Some(value()?.into()),
+            "--format" => parsed.format = value()?.parse()?,
+            "--verify" => parsed.verify = true,
+            "-j" | "--jobs" => parsed.jobs = Some(parse_count(&value()?)?),
+            "--visualize" => parsed.visualize = Some(value()?.parse()?),
+            "--save-baseline" => parsed.save_baseline = true,
+            "--compare-baseline" => parsed.compare_baseline = true,
+            "--baseline" => parsed.baseline = Some(value()?.into()),
+            "--threshold" => {
+                let threshold = value()?;
+                parsed.threshold = Some(
+                    threshold
+                        .parse()
+                        .ok()
+                        .filter(|t: &f64| *t >= 0.0)
+                        .ok_or(format!("invalid threshold '{threshold}'"))?,
+                )
+            }
+            "--answers" => parsed.answers = Some(value()?.into()),
+            "--variant" => parsed.variant = Some(value()?),
+            "-h" | "--help" => return Ok(None),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if let Some(days) = &parsed.days {
+        if let Some(missing) = days.iter().find(|d| day(**d).is_none()) {
+            return Err(format!(
+                "day {missing} is not implemented (available: {})",
+                DAYS.iter()
+                    .map(|d| d.day.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+    }
+
+    if let Some(variant) = &parsed.variant {
+        let days = || DAYS.iter().filter(|d| parsed.runs_day(d.day));
+        if days().all(|d| d.variant(variant).is_none()) {
+            let available: Vec<_> = days()
+                .flat_map(|d| {
+                    d.variant_names()
+                        .map(move |name| format!("{name} (day {})", d.day))
+                })
+                .collect();
+            return Err(format!(
+                "none of the selected days has a variant '{variant}' (available: {})",
+                match available.is_empty() {
+                    true => "none".to_string(),
+                    false => available.join(", "),
+                }
+            ));
+        }
+    }
+
+    if parsed.jobs.is_some_and(|jobs| jobs > 1)
+        && (parsed.bench.is_some() || parsed.save_baseline || parsed.compare_baseline)
+    {
+        return Err(
+            "--jobs can't be used with --bench, --save-baseline or --compare-baseline, the timings \
+             would be skewed by the days competing for cores"
+                .to_string(),
+        );
+    }
+
+    for input in inputs {
+        let (day, source) = match input.split_once('=') {
+            Some((day, source)) => (
+                day.parse().map_err(|_| format!("invalid day '{day}'"))?,
+                source,
+            ),
+            None => match parsed.days.as_deref() {
+                Some(&[day]) => (day, input.as_str()),
+                _ => {
+                    return Err(format!(
+                    "--input '{input}' must be given as DAY=FILE unless a single --day is selected"
+                ))
+                }
+            },
+        };
+        if advent_of_code_2024::day(day).is_none() {
+            return Err(format!(
+                "--input '{input}' is for day {day}, which is not implemented"
+            ));
+        }
+        if !parsed.runs_day(day) {
+            return Err(format!(
+                "--input '{input}' is for day {day}, which isn't selected with --day"
+            ));
+        }
+        parsed.inputs.push((day, Source::parse(source)));
+    }
+
+    if parsed
+        .inputs
+        .iter()
+        .filter(|(_, source)| *source == Source::Stdin)
+        .count()
+        > 1
+    {
+        return Err("stdin can only be used as the input for one day".to_string());
+    }
+
+    Ok(Some(parsed))
+}
+
+fn new_day(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut day = None;
+    let mut input_dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(PathBuf::from(
+                    args.next().ok_or("missing value for '--input-dir'")?,
+                ))
+            }
+            _ if day.is_none() => {
+                day = Some(arg.parse().map_err(|_| format!("invalid day '{arg}'"))?);
+            }
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    let day = day.ok_or("missing day")?;
+
+    let src_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let input_dir = input::input_dir(input_dir.as_deref());
+    for path in scaffold::new_day(&src_dir, &input_dir, day)? {
+        println!("created {}", path.display());
+    }
+    Ok(())
+}
+
+fn fetch(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut days = None;
+    let mut input_dir = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input-dir" => {
+                input_dir = Some(PathBuf::from(
+                    args.next().ok_or("missing value for '--input-dir'")?,
+                ))
+            }
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    let days = days.ok_or("missing day")?;
+
+    let input_dir = input::input_dir(input_dir.as_deref());
+    let mut client = client::Client::new(client::Config::from_env());
+    for day in days {
+        match client::input::cached_input(&mut client, &input_dir, day) {
+            Ok((path, true)) => println!("day {day}: downloaded to {}", path.display()),
+            Ok((path, false)) => println!("day {day}: already have {}", path.display()),
+            Err(e @ client::Error::NotUnlocked { .. }) => return Err(e.to_string()),
+            Err(e) => return Err(format!("day {day}: {e}")),
+        }
+    }
+    Ok(())
 }
 
-macro_rules! run_day_with_generator {
-    ($m:ident, $d:expr) => {
-        run_parts!($m, $d, |i| $m::input_generator(i));
+fn submit(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut answer = None;
+    let mut history_path = None;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answer" => answer = Some(args.next().ok_or("missing value for '--answer'")?),
+            "--history" => {
+                history_path = Some(PathBuf::from(
+                    args.next().ok_or("missing value for '--history'")?,
+                ))
+            }
+            _ => rest.push(arg),
+        }
+    }
+    let args = parse_args(rest.into_iter())?.ok_or(USAGE)?;
+    let (Some(&[n]), Some(part)) = (args.days.as_deref(), args.part) else {
+        return Err("submit needs a single --day and a --part".to_string());
     };
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = args.resolve(day(n).expect("days are validated by parse_args"));
+            let report = match run_day(&args, day) {
+                Outcome::Ran(report) => report,
+                Outcome::Skipped(_) => {
+                    let (source, _) = args.input_source(n);
+                    return Err(format!("no input for day {n} at {source}"));
+                }
+                Outcome::Failed(e) => return Err(e.trim_end().to_string()),
+            };
+            report.answers[part as usize - 1]
+                .clone()
+                .ok_or(format!("day {n} part {part} has no answer"))?
+        }
+    };
+
+    let history_path = history_path.unwrap_or(PathBuf::from(submit::DEFAULT_HISTORY_PATH));
+    let mut history = submit::History::load(&history_path)
+        .map_err(|e| format!("failed to read {}: {e}", history_path.display()))?;
+    history
+        .check(n, part, &answer)
+        .map_err(|e| format!("not submitting, {e}"))?;
+
+    let mut client = client::Client::new(client::Config::from_env());
+    let outcome = submit::submit(&mut client, n, part, &answer).map_err(|e| e.to_string())?;
+    println!("day {n}-{part}: {answer}: {outcome}");
+
+    let correct = outcome == submit::Outcome::Correct;
+    history
+        .record(
+            &history_path,
+            submit::Submission {
+                day: n,
+                part,
+                answer,
+                outcome,
+            },
+        )
+        .map_err(|e| format!("failed to write {}: {e}", history_path.display()))?;
+    match correct {
+        true => Ok(()),
+        false => Err("the answer wasn't accepted".to_string()),
+    }
 }
 
-#[allow(unused)]
-macro_rules! run_day {
-    ($m:ident, $d:expr) => {
-        run_parts!($m, $d);
+fn visualize(args: &Args, style: Visualization) -> Result<(), String> {
+    let Some(&[n]) = args.days.as_deref() else {
+        return Err("--visualize needs a single --day".to_string());
     };
+
+    let (source, _) = args.input_source(n);
+    let input = source
+        .read()
+        .map_err(|e| format!("failed to read input from {source}: {e}"))?;
+
+    let mut out = std::io::stdout().lock();
+    for part in [1, 2].into_iter().filter(|&p| args.runs_part(p)) {
+        args.resolve(day(n).expect("days are validated by parse_args"))
+            .visualize(&input, part, style, &mut out)
+            .map_err(|e| format!("day {n}: {e}"))?;
+    }
+    Ok(())
 }
 
-pub fn main() {
+pub fn main() -> ExitCode {
+    let mut argv = std::env::args().skip(1).peekable();
+    let command: Option<fn(_) -> _> = match argv.peek().map(String::as_str) {
+        Some("new-day") => Some(new_day),
+        Some("fetch") => Some(fetch),
+        Some("submit") => Some(submit),
+        _ => None,
+    };
+    if let Some(command) = command {
+        argv.next();
+        return match command(argv) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let args = match parse_args(argv) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(style) = args.visualize {
+        return match visualize(&args, style) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let expected = match args.verify {
+        true => {
+            let path = args
+                .answers
+                .as_deref()
+                .unwrap_or(Path::new(verify::DEFAULT_ANSWERS_PATH));
+            match Expected::load(path) {
+                Ok(expected) => Some(expected),
+                Err(e) => {
+                    eprintln!("error: {e}");
+                    return ExitCode::from(2);
+                }
+            }
+        }
+        false => None,
+    };
+
+    let baseline_path = args
+        .baseline
+        .as_deref()
+        .unwrap_or(Path::new(baseline::DEFAULT_BASELINE_PATH));
+    let baseline = match (
+        args.compare_baseline || args.save_baseline,
+        baseline_path.exists(),
+    ) {
+        (true, true) => match Baseline::load(baseline_path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::from(2);
+            }
+        },
+        (true, false) if args.compare_baseline => {
+            eprintln!("error: no baseline at {}", baseline_path.display());
+            return ExitCode::from(2);
+        }
+        _ => None,
+    };
+
     let instant = Instant::now();
-    run_day_with_generator!(day_01, "1");
-    run_day_with_generator!(day_02, "2");
-    run_day_with_generator!(day_03, "3");
-    run_day_with_generator!(day_04, "4");
-    run_day_with_generator!(day_05, "5");
-    run_day_with_generator!(day_06, "6");
-    run_day_with_generator!(day_07, "7");
-    run_day_with_generator!(day_08, "8");
-    run_day_with_generator!(day_09, "9");
-    run_day_with_generator!(day_10, "10");
-    run_day_with_generator!(day_11, "11");
-    run_day_with_generator!(day_12, "12");
-    run_day_with_generator!(day_13, "13");
-
-    println!("done in {:?}", instant.elapsed());
+    let mut ok = true;
+    let mut reports = vec![];
+    let days: Vec<_> = DAYS
+        .iter()
+        .filter(|d| args.runs_day(d.day))
+        .map(|d| args.resolve(d))
+        .collect();
+    let jobs = args.jobs.unwrap_or(1);
+    let total = run_days(&args, &days, jobs, |outcome| match outcome {
+        Outcome::Skipped(warning) => eprintln!("{warning}"),
+        Outcome::Failed(e) => {
+            eprintln!("{e}");
+            ok = false;
+        }
+        Outcome::Ran(report) => {
+            if args.format == Format::Text {
+                print_report(&args, expected.as_ref(), &report);
+            }
+            reports.push(*report);
+        }
+    });
+    let elapsed = instant.elapsed();
+
+    let threads = match jobs {
+        1 => "1 thread".to_string(),
+        _ => format!("{jobs} threads"),
+    };
+    let cpu = match total {
+        Some(cpu) => format!("{cpu:?} CPU time"),
+        None => "CPU time unavailable".to_string(),
+    };
+    let done = format!("done in {elapsed:?} ({cpu} on {threads})");
+
+    let stdout = std::io::stdout().lock();
+    let written = match args.format {
+        Format::Text => {
+            println!("{done}");
+            Ok(())
+        }
+        Format::Json => output::write_json(stdout, &reports, expected.as_ref()),
+        Format::Csv => output::write_csv(stdout, &reports, expected.as_ref()),
+    };
+    if args.format != Format::Text {
+        // keep stdout machine-readable
+        eprintln!("{done}");
+    }
+    if let Err(e) = written {
+        eprintln!("failed to write output: {e}");
+        ok = false;
+    }
+
+    if let Some(expected) = &expected {
+        let (mut pass, mut fail, mut unknown) = (0, 0, 0);
+        for entry in output::entries(&reports) {
+            match expected.check(entry.day, entry.part, entry.answer) {
+                Verdict::Pass => pass += 1,
+                Verdict::Fail { .. } => fail += 1,
+                Verdict::Unknown => unknown += 1,
+            }
+        }
+        eprintln!("verify: {pass} passed, {fail} failed, {unknown} unknown");
+        ok &= fail == 0;
+    }
+
+    if let Some(baseline) = baseline.as_ref().filter(|_| args.compare_baseline) {
+        let threshold = args.threshold.unwrap_or(10.0) / 100.0;
+        let comparisons = baseline.compare(&reports, threshold);
+        let regressions = comparisons.iter().filter(|c| c.regressed).count();
+        eprintln!();
+        if let Err(e) = baseline::write_table(std::io::stderr(), &comparisons) {
+            eprintln!("failed to write baseline comparison: {e}");
+        }
+        eprintln!(
+            "baseline: {regressions} of {} steps slower than the baseline by more than {:.0}%",
+            comparisons.len(),
+            threshold * 100.0
+        );
+        ok &= regressions == 0;
+    }
+
+    if args.save_baseline {
+        let mut saved = baseline.unwrap_or_default();
+        saved.update(Baseline::from_reports(&reports));
+        match saved.save(baseline_path) {
+            Ok(()) => eprintln!("saved baseline to {}", baseline_path.display()),
+            Err(e) => {
+                eprintln!(
+                    "failed to save baseline to {}: {e}",
+                    baseline_path.display()
+                );
+                ok = false;
+            }
+        }
+    }
+
+    if args.bench.is_some() {
+        let path = args
+            .bench_output
+            .as_deref()
+            .unwrap_or(Path::new("bench-results.tsv"));
+        match runner::bench::append_results(path, &reports) {
+            Ok(()) => eprintln!("wrote benchmark results to {}", path.display()),
+            Err(e) => {
+                eprintln!(
+                    "failed to write benchmark results to {}: {e}",
+                    path.display()
+                );
+                ok = false;
+            }
+        }
+    }
+
+    match ok {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
 }
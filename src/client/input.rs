@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use super::{Client, Error};
+
+pub fn fetch_input(client: &mut Client, day: u8) -> Result<String, Error> {
+    let url = format!("{}/input", client.day_url(day));
+    let response = client.request(&url, None)?;
+    match response.status {
+        200 => Ok(response.body),
+        400 => Err(Error::BadSession),
+        404 => Err(Error::NotUnlocked { day }),
+        status => Err(Error::Status {
+            status,
+            body: response.body,
+        }),
+    }
+}
+
+/// Returns the path of the input for `day` in `input_dir`, downloading it first if it isn't
+/// there. Inputs never change, so one that's already on disk is never downloaded again (empty
+/// placeholder files don't count).
+///
+/// The returned flag is whether the input was downloaded.
+pub fn cached_input(
+    client: &mut Client,
+    input_dir: &Path,
+    day: u8,
+) -> Result<(PathBuf, bool), Error> {
+    let path = crate::input::day_path(input_dir, day);
+    if path.metadata().is_ok_and(|m| m.len() > 0) {
+        return Ok((path, false));
+    }
+
+    let input = fetch_input(client, day)?;
+    std::fs::create_dir_all(input_dir)?;
+    std::fs::write(&path, input)?;
+    Ok((path, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::Fake;
+
+    #[test]
+    fn test_cached_input() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let (mut client, requests) = Fake::client(&[
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (200, "1 2 3\n"),
+            (400, "Puzzle inputs differ by user."),
+        ]);
+
+        assert!(matches!(
+            cached_input(&mut client, &dir, 6),
+            Err(Error::NotUnlocked { day: 6 })
+        ));
+        assert_eq!(
+            cached_input(&mut client, &dir, 6).unwrap(),
+            (dir.join("day6.txt"), true)
+        );
+        assert_eq!(
+            cached_input(&mut client, &dir, 6).unwrap(),
+            (dir.join("day6.txt"), false)
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day6.txt")).unwrap(),
+            "1 2 3\n"
+        );
+        assert!(matches!(
+            fetch_input(&mut client, 7),
+            Err(Error::BadSession)
+        ));
+
+        assert_eq!(
+            requests
+                .borrow()
+                .iter()
+                .map(|r| r.0.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "http://localhost:8080/2024/day/6/input",
+                "http://localhost:8080/2024/day/6/input",
+                "http://localhost:8080/2024/day/7/input",
+            ]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+//! A client for adventofcode.com, or anything that serves the same endpoints (e.g. a local
+//! stand-in server for testing).
+
+use std::{
+    fmt,
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+pub mod input;
+pub mod submit;
+
+pub const YEAR: u16 = 2024;
+
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const SESSION_FILE_ENV: &str = "AOC_SESSION_FILE";
+pub const DEFAULT_SESSION_FILE: &str = "cookies";
+
+/// The minimum time between two requests from the same [`Client`].
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+const USER_AGENT: &str = "github.com/zaneduffield/advent-of-code-2024";
+
+#[derive(Debug)]
+pub enum Error {
+    NoSession,
+    /// The puzzle (or its input) isn't available yet
+    NotUnlocked {
+        day: u8,
+    },
+    /// A 400 response, which is what the server gives for a missing or expired session
+    BadSession,
+    Status {
+        status: u16,
+        body: String,
+    },
+    Transport(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSession => write!(
+                f,
+                "no session token, set {SESSION_ENV} or put it in {DEFAULT_SESSION_FILE}"
+            ),
+            Error::NotUnlocked { day } => write!(f, "day {day} isn't unlocked yet"),
+            Error::BadSession => write!(f, "the session token was rejected, it may have expired"),
+            Error::Status { status, body } => {
+                write!(f, "unexpected response ({status}): {}", body.trim())
+            }
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+            Error::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Performs the actual HTTP requests, so that tests can stand in for the network.
+pub trait Transport {
+    /// Sends a GET, or a POST of `form` (already url-encoded) if there is one.
+    fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, Error>;
+}
+
+/// Shells out to `curl`, which saves pulling in an HTTP and TLS stack for a handful of requests.
+pub struct Curl;
+
+impl Transport for Curl {
+    fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, Error> {
+        // write the status code on its own line after the body
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--write-out", "\n%{http_code}"])
+            .args(["--user-agent", USER_AGENT])
+            // the session goes in a config on stdin, so it doesn't show up in `ps`
+            .args(["--config", "-"]);
+        if let Some(form) = form {
+            cmd.args(["--data", form]);
+        }
+        let run_failed = |e: io::Error| Error::Transport(format!("failed to run curl: {e}"));
+        let mut child = cmd
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(run_failed)?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(curl_config(session).as_bytes())
+            .map_err(run_failed)?;
+        let output = child.wait_with_output().map_err(run_failed)?;
+
+        if !output.status.success() {
+            return Err(Error::Transport(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let out = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = out.rsplit_once('\n').unwrap_or(("", &out));
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| Error::Transport(format!("unexpected status '{status}' from curl")))?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// A curl config file that sends the session cookie.
+fn curl_config(session: &str) -> String {
+    let quoted = session.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("cookie = \"session={quoted}\"\n")
+}
+
+pub struct Config {
+    pub base_url: String,
+    pub session: Option<String>,
+    pub min_interval: Duration,
+}
+
+impl Config {
+    /// Reads the base URL from [`BASE_URL_ENV`], and the session token from [`SESSION_ENV`] or
+    /// the file named by [`SESSION_FILE_ENV`] (default [`DEFAULT_SESSION_FILE`]).
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let session = var(SESSION_ENV).or_else(|| {
+            let path = var(SESSION_FILE_ENV).unwrap_or(DEFAULT_SESSION_FILE.to_string());
+            std::fs::read_to_string(Path::new(&path)).ok()
+        });
+
+        Config {
+            base_url: var(BASE_URL_ENV).unwrap_or(DEFAULT_BASE_URL.to_string()),
+            session: session.map(|s| parse_session(&s)).filter(|s| !s.is_empty()),
+            min_interval: DEFAULT_MIN_INTERVAL,
+        }
+    }
+}
+
+/// Accepts either the bare token or the `session=<token>` cookie form.
+fn parse_session(s: &str) -> String {
+    let s = s.trim();
+    s.strip_prefix("session=").unwrap_or(s).to_string()
+}
+
+pub struct Client {
+    config: Config,
+    transport: Box<dyn Transport>,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Self::with_transport(config, Box::new(Curl))
+    }
+
+    pub fn with_transport(config: Config, transport: Box<dyn Transport>) -> Self {
+        Client {
+            config,
+            transport,
+            last_request: None,
+        }
+    }
+
+    pub fn day_url(&self, day: u8) -> String {
+        format!(
+            "{}/{YEAR}/day/{day}",
+            self.config.base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Sends a request to `url`, waiting first if the last one was too recent.
+    pub fn request(&mut self, url: &str, form: Option<&str>) -> Result<Response, Error> {
+        let session = self.config.session.as_deref().ok_or(Error::NoSession)?;
+
+        if let Some(last) = self.last_request {
+            thread::sleep(self.config.min_interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+
+        self.transport.request(url, session, form)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    /// `(url, form)` of each request made
+    pub type Requests = Rc<RefCell<Vec<(String, Option<String>)>>>;
+
+    /// Responds to each request with the next of `responses`, recording the requests made.
+    pub struct Fake {
+        pub responses: RefCell<Vec<(u16, &'static str)>>,
+        pub requests: Requests,
+    }
+
+    impl Fake {
+        pub fn client(responses: &[(u16, &'static str)]) -> (Client, Requests) {
+            let requests = Rc::default();
+            let fake = Fake {
+                responses: RefCell::new(responses.iter().rev().copied().collect()),
+                requests: Rc::clone(&requests),
+            };
+            let config = Config {
+                base_url: "http://localhost:8080/".to_string(),
+                session: Some("token".to_string()),
+                min_interval: Duration::ZERO,
+            };
+            (Client::with_transport(config, Box::new(fake)), requests)
+        }
+    }
+
+    impl Transport for Fake {
+        fn request(&self, url: &str, session: &str, form: Option<&str>) -> Result<Response, Error> {
+            assert_eq!(session, "token");
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), form.map(str::to_string)));
+            let (status, body) = self
+                .responses
+                .borrow_mut()
+                .pop()
+                .expect("no more responses");
+            Ok(Response {
+                status,
+                body: body.to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_session_and_rate_limit() {
+        assert_eq!(parse_session("session=abc\n"), "abc");
+        assert_eq!(parse_session(" abc "), "abc");
+        assert_eq!(curl_config("abc"), "cookie = \"session=abc\"\n");
+        assert_eq!(
+            curl_config(r#"a"b\c"#),
+            r#"cookie = "session=a\"b\\c""#.to_string() + "\n"
+        );
+
+        let (mut client, _) = Fake::client(&[(200, ""), (200, "")]);
+        assert_eq!(client.day_url(6), "http://localhost:8080/2024/day/6");
+
+        client.config.min_interval = Duration::from_millis(50);
+        let instant = Instant::now();
+        client.request("a", None).unwrap();
+        client.request("b", None).unwrap();
+        assert!(instant.elapsed() >= Duration::from_millis(50));
+
+        client.config.session = None;
+        assert!(matches!(client.request("c", None), Err(Error::NoSession)));
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use super::{Client, Error};
+
+pub const DEFAULT_HISTORY_PATH: &str = "submissions.tsv";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong(Option<Hint>),
+    /// Submitted too soon after the last one, with the time left to wait if given
+    Wait(Option<String>),
+    /// The part was already solved, or isn't unlocked yet
+    WrongLevel,
+}
+
+impl Outcome {
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong(None) => "wrong",
+            Outcome::Wrong(Some(Hint::TooHigh)) => "too-high",
+            Outcome::Wrong(Some(Hint::TooLow)) => "too-low",
+            Outcome::Wait(_) => "wait",
+            Outcome::WrongLevel => "wrong-level",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "correct" => Outcome::Correct,
+            "wrong" => Outcome::Wrong(None),
+            "too-high" => Outcome::Wrong(Some(Hint::TooHigh)),
+            "too-low" => Outcome::Wrong(Some(Hint::TooLow)),
+            "wait" => Outcome::Wait(None),
+            "wrong-level" => Outcome::WrongLevel,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::Wrong(None) => write!(f, "that's not the right answer"),
+            Outcome::Wrong(Some(Hint::TooHigh)) => write!(f, "that's too high"),
+            Outcome::Wrong(Some(Hint::TooLow)) => write!(f, "that's too low"),
+            Outcome::Wait(Some(left)) => write!(f, "answered too recently, {left} left to wait"),
+            Outcome::Wait(None) => write!(f, "answered too recently"),
+            Outcome::WrongLevel => write!(f, "that part is already solved or not unlocked yet"),
+        }
+    }
+}
+
+/// Picks the outcome out of the response page.
+pub fn parse_response(body: &str) -> Option<Outcome> {
+    if body.contains("That's the right answer") {
+        Some(Outcome::Correct)
+    } else if body.contains("That's not the right answer") {
+        Some(Outcome::Wrong(
+            if body.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if body.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            },
+        ))
+    } else if body.contains("You gave an answer too recently") {
+        // "You have 1m 3s left to wait."
+        let left = body
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(left, _)| left.to_string());
+        Some(Outcome::Wait(left))
+    } else if body.contains("You don't seem to be solving the right level") {
+        Some(Outcome::WrongLevel)
+    } else {
+        None
+    }
+}
+
+pub fn submit(client: &mut Client, day: u8, part: u8, answer: &str) -> Result<Outcome, Error> {
+    let url = format!("{}/answer", client.day_url(day));
+    let form = format!("level={part}&answer={}", url_encode(answer));
+    let response = client.request(&url, Some(&form))?;
+    match response.status {
+        200 => parse_response(&response.body).ok_or(Error::Status {
+            status: 200,
+            body: "unrecognised response to the submission".to_string(),
+        }),
+        400 => Err(Error::BadSession),
+        404 => Err(Error::NotUnlocked { day }),
+        status => Err(Error::Status {
+            status,
+            body: response.body,
+        }),
+    }
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// Every answer submitted so far, stored one tab-separated `TIMESTAMP DAY PART ANSWER OUTCOME`
+/// per line.
+#[derive(Debug, Default)]
+pub struct History {
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn parse(input: &str) -> Self {
+        let submissions = input
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t').skip(1);
+                Some(Submission {
+                    day: fields.next()?.parse().ok()?,
+                    part: fields.next()?.parse().ok()?,
+                    answer: fields.next()?.to_string(),
+                    outcome: Outcome::from_name(fields.next()?)?,
+                })
+            })
+            .collect();
+        History { submissions }
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(input) => Ok(Self::parse(&input)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn record(&mut self, path: &Path, submission: Submission) -> io::Result<()> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(
+            file,
+            "{timestamp}\t{}\t{}\t{}\t{}",
+            submission.day,
+            submission.part,
+            submission.answer,
+            submission.outcome.name()
+        )?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    /// Explains why `answer` shouldn't be submitted, if the history already tells us how it
+    /// would go.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), String> {
+        let past = self
+            .submissions
+            .iter()
+            .filter(|s| (s.day, s.part) == (day, part));
+
+        let num = answer.parse::<i128>().ok();
+        for s in past {
+            match &s.outcome {
+                Outcome::Correct if s.answer == answer => {
+                    return Err(format!("{answer} was already accepted"))
+                }
+                Outcome::Correct => {
+                    return Err(format!("already solved with {}", s.answer));
+                }
+                Outcome::Wrong(_) if s.answer == answer => {
+                    return Err(format!("{answer} was already rejected"))
+                }
+                Outcome::Wrong(Some(hint)) => {
+                    let Some((num, bound)) = num.zip(s.answer.parse::<i128>().ok()) else {
+                        continue;
+                    };
+                    match hint {
+                        Hint::TooHigh if num >= bound => {
+                            return Err(format!("{answer} is too high, {bound} already was"))
+                        }
+                        Hint::TooLow if num <= bound => {
+                            return Err(format!("{answer} is too low, {bound} already was"))
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::tests::Fake;
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response("<article><p>That's the right answer! You are one gold star closer"),
+            Some(Outcome::Correct)
+        );
+        assert_eq!(
+            parse_response("That's not the right answer; your answer is too high. Please wait"),
+            Some(Outcome::Wrong(Some(Hint::TooHigh)))
+        );
+        assert_eq!(
+            parse_response("That's not the right answer. If you're stuck"),
+            Some(Outcome::Wrong(None))
+        );
+        assert_eq!(
+            parse_response("You gave an answer too recently; You have 41s left to wait."),
+            Some(Outcome::Wait(Some("41s".to_string())))
+        );
+        assert_eq!(
+            parse_response("You don't seem to be solving the right level."),
+            Some(Outcome::WrongLevel)
+        );
+        assert_eq!(parse_response("<html>"), None);
+    }
+
+    #[test]
+    fn test_history() {
+        let history = History::parse(
+            "1\t6\t1\t100\ttoo-high\n2\t6\t1\t50\ttoo-low\n3\t6\t1\t77\twrong\n4\t6\t2\t5\tcorrect\n",
+        );
+        assert!(history.check(6, 1, "60").is_ok());
+        assert!(history.check(6, 1, "100").is_err());
+        assert!(history.check(6, 1, "101").is_err());
+        assert!(history.check(6, 1, "50").is_err());
+        assert!(history.check(6, 1, "77").is_err());
+        assert!(history.check(6, 2, "6").is_err());
+        assert!(history.check(7, 1, "100").is_ok());
+    }
+
+    #[test]
+    fn test_submit() {
+        let (mut client, requests) = Fake::client(&[(200, "That's the right answer!")]);
+        assert_eq!(submit(&mut client, 6, 2, "a b").unwrap(), Outcome::Correct);
+        assert_eq!(
+            requests.borrow()[0],
+            (
+                "http://localhost:8080/2024/day/6/answer".to_string(),
+                Some("level=2&answer=a%20b".to_string())
+            )
+        );
+    }
+}
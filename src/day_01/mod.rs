@@ -5,6 +5,8 @@ use winnow::{
     Parser,
 };
 
+use crate::parse::ParseError;
+
 pub struct Input {
     left: Vec<u32>,
     right: Vec<u32>,
@@ -20,8 +22,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     })
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 pub fn part_1(input: &Input) -> u32 {
@@ -45,6 +47,8 @@ pub fn part_2(input: &Input) -> u32 {
     sum
 }
 
+solution!(Day1, 1, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,14 +65,14 @@ mod tests {
             3   9
             3   3
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 11);
         assert_eq!(part_2(&input), 31);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day1.txt"));
+        let input = input_generator(include_str!("../../input/2024/day1.txt")).unwrap();
         assert_eq!(part_1(&input), 1580061);
         assert_eq!(part_2(&input), 23046913);
     }
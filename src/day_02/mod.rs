@@ -5,6 +5,8 @@ use winnow::{
     Parser,
 };
 
+use crate::parse::ParseError;
+
 #[derive(Debug)]
 pub struct Report {
     nums: Vec<u32>,
@@ -26,8 +28,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
         .parse_next(input)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 impl Report {
@@ -77,6 +79,8 @@ pub fn part_2(input: &Input) -> u32 {
     solve(input, true)
 }
 
+solution!(Day2, 2, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,14 +97,14 @@ mod tests {
             8 6 4 4 1
             1 3 6 7 9
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 2);
         assert_eq!(part_2(&input), 4);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day2.txt"));
+        let input = input_generator(include_str!("../../input/2024/day2.txt")).unwrap();
         assert_eq!(part_1(&input), 598);
         assert_eq!(part_2(&input), 634);
     }
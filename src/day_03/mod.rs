@@ -5,6 +5,8 @@ use winnow::{
     Parser,
 };
 
+use crate::parse::ParseError;
+
 pub type Input = Vec<Instruction>;
 
 pub enum Instruction {
@@ -49,8 +51,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     Ok(instructions)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 pub fn part_1(input: &Input) -> u32 {
@@ -82,6 +84,8 @@ pub fn part_2(input: &Input) -> u32 {
         .sum()
 }
 
+solution!(Day3, 3, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,17 +94,19 @@ mod tests {
     fn test() {
         let input = input_generator(
             "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
-        );
+        )
+        .unwrap();
         assert_eq!(part_1(&input), 161);
         let input = input_generator(
             "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
-        );
+        )
+        .unwrap();
         assert_eq!(part_2(&input), 48);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day3.txt"));
+        let input = input_generator(include_str!("../../input/2024/day3.txt")).unwrap();
         assert_eq!(part_1(&input), 166357705);
         assert_eq!(part_2(&input), 88811886);
     }
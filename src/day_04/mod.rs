@@ -1,53 +1,30 @@
-pub struct Input<'a> {
-    data: Vec<&'a [u8]>,
-}
+use crate::{
+    grid::{Grid, Vec2},
+    parse::ParseError,
+};
 
-pub fn input_generator(input: &str) -> Input {
-    Input {
-        data: input.lines().map(|s| s.as_bytes()).collect(),
-    }
+pub type Input = Grid<u8>;
+
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input)
 }
 
 pub fn part_1(input: &Input) -> u32 {
     let mut solutions = 0;
-    for (y, row) in input.data.iter().enumerate() {
-        for (x, col) in row.iter().enumerate() {
-            if *col != b'X' {
-                continue;
-            }
-
-            for (dx, dy) in [
-                (-1, 0),
-                (-1, -1),
-                (0, -1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-                (0, 1),
-                (-1, 1),
-            ] {
-                const TARGET: &[u8] = b"XMAS";
-                let mut pos = 1usize;
-
-                let mut x = x as isize;
-                let mut y = y as isize;
-
-                loop {
-                    x += dx;
-                    y += dy;
+    for (pos, &cell) in input.iter() {
+        if cell != b'X' {
+            continue;
+        }
 
-                    if y.try_into().ok().zip(x.try_into().ok()).and_then(
-                        |(y, x): (usize, usize)| input.data.get(y).and_then(|row| row.get(x)),
-                    ) != TARGET.get(pos)
-                    {
-                        break;
-                    }
-                    pos += 1;
-                    if pos >= TARGET.len() {
-                        solutions += 1;
-                        break;
-                    }
-                }
+        for delta in Vec2::ALL {
+            const TARGET: &[u8] = b"MAS";
+            if input
+                .ray(pos, delta)
+                .take(TARGET.len())
+                .map(|p| input[p])
+                .eq(TARGET.iter().copied())
+            {
+                solutions += 1;
             }
         }
     }
@@ -57,32 +34,19 @@ pub fn part_1(input: &Input) -> u32 {
 
 pub fn part_2(input: &Input) -> u32 {
     let mut solutions = 0;
-    for (y, row) in input.data.iter().enumerate() {
-        'row: for (x, col) in row.iter().enumerate() {
-            if *col != b'A' {
-                continue;
-            }
-
-            let mut corners =
-                [(-1, -1), (1, -1), (1, 1), (-1, 1)].map(|(dx, dy): (isize, isize)| {
-                    let x = x as isize + dx;
-                    let y = y as isize + dy;
+    'cell: for (pos, &cell) in input.iter() {
+        if cell != b'A' {
+            continue;
+        }
 
-                    y.try_into()
-                        .ok()
-                        .zip(x.try_into().ok())
-                        .and_then(|(y, x): (usize, usize)| {
-                            input.data.get(y).and_then(|row| row.get(x).cloned())
-                        })
-                        .unwrap_or(b'0')
-                });
+        let mut corners = Vec2::DIAGONAL
+            .map(|delta| input.offset(pos, delta).map_or(b'0', |p| input[p]));
 
-            for _ in 0..4 {
-                corners.rotate_left(1);
-                if corners == *b"MSSM" {
-                    solutions += 1;
-                    continue 'row;
-                }
+        for _ in 0..4 {
+            corners.rotate_left(1);
+            if corners == *b"MSSM" {
+                solutions += 1;
+                continue 'cell;
             }
         }
     }
@@ -90,6 +54,8 @@ pub fn part_2(input: &Input) -> u32 {
     solutions
 }
 
+solution!(Day4, 4, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,14 +76,14 @@ mod tests {
             MAMMMXMMMM
             MXMXAXMASX
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 18);
         assert_eq!(part_2(&input), 9);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day4.txt"));
+        let input = input_generator(include_str!("../../input/2024/day4.txt")).unwrap();
         assert_eq!(part_1(&input), 2532);
         assert_eq!(part_2(&input), 1941);
     }
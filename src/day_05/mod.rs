@@ -1,9 +1,16 @@
+use crate::parse::ParseError;
+
 pub struct Input {
     before_map: [Vec<u8>; 256],
     pages: Vec<Vec<u8>>,
 }
 
-fn parse_input(input: &str) -> Input {
+fn parse_num(input: &str, num: &str) -> Result<u8, ParseError> {
+    num.parse()
+        .map_err(|_| ParseError::at_substr(input, num, "value should parse into u8"))
+}
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
     let mut before_map = std::array::from_fn(|_| Vec::with_capacity(8));
     let mut lines = input.lines();
     for line in lines.by_ref() {
@@ -12,27 +19,20 @@ fn parse_input(input: &str) -> Input {
         }
         let (left, right) = line
             .split_once("|")
-            .expect("lines should be delimited by |");
-        let (left, right): (u8, u8) = (
-            left.parse().expect("value should parse into u8"),
-            right.parse().expect("value should parse into u8"),
-        );
+            .ok_or_else(|| ParseError::at_substr(input, line, "lines should be delimited by |"))?;
+        let (left, right) = (parse_num(input, left)?, parse_num(input, right)?);
 
         before_map[left as usize].push(right);
     }
 
     let pages = lines
-        .map(|line| {
-            line.split(',')
-                .map(|n| n.parse::<u8>().expect("value should parse into u8"))
-                .collect()
-        })
-        .collect();
+        .map(|line| line.split(',').map(|n| parse_num(input, n)).collect())
+        .collect::<Result<_, _>>()?;
 
-    Input { before_map, pages }
+    Ok(Input { before_map, pages })
 }
 
-pub fn input_generator(input: &str) -> Input {
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
     parse_input(input)
 }
 
@@ -96,6 +96,8 @@ pub fn part_2(input: &Input) -> u32 {
         .sum()
 }
 
+solution!(Day5, 5, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,14 +136,14 @@ mod tests {
             61,13,29
             97,13,75,29,47
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 143);
         assert_eq!(part_2(&input), 123);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day5.txt"));
+        let input = input_generator(include_str!("../../input/2024/day5.txt")).unwrap();
         assert_eq!(part_1(&input), 4637);
         assert_eq!(part_2(&input), 6370);
     }
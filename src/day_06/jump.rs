@@ -0,0 +1,141 @@
+use super::{walk::State, Point};
+use crate::{
+    grid::{Dir, Grid, Pos},
+    search::{Bitmap, Visited},
+};
+
+/// Where the guard stops in front of the next obstacle from each `(pos, dir)`, so a walk can
+/// skip straight from one turn to the next.
+pub struct JumpTable {
+    width: usize,
+    /// Indexed by [`state_index`], `None` where the guard would walk off the grid
+    stops: Vec<Option<Pos>>,
+}
+
+fn state_index(width: usize, pos: Pos, dir: Dir) -> usize {
+    (pos.row * width + pos.col) * 4 + dir as usize
+}
+
+impl JumpTable {
+    pub fn new(grid: &Grid<Point>) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let mut stops = vec![None; width * height * 4];
+
+        // sweep each line against the direction of travel, remembering the last stop seen
+        for dir in Dir::ALL {
+            let lines: Vec<Vec<Pos>> = match dir {
+                Dir::Up | Dir::Down => (0..width)
+                    .map(|col| (0..height).map(|row| Pos::new(row, col)).collect())
+                    .collect(),
+                Dir::Left | Dir::Right => (0..height)
+                    .map(|row| (0..width).map(|col| Pos::new(row, col)).collect())
+                    .collect(),
+            };
+            for mut line in lines {
+                if matches!(dir, Dir::Down | Dir::Right) {
+                    line.reverse();
+                }
+                let mut stop = None;
+                for pos in line {
+                    match grid[pos] {
+                        Point::Full => stop = grid.offset(pos, dir.reverse()),
+                        Point::Empty => stops[state_index(width, pos, dir)] = stop,
+                    }
+                }
+            }
+        }
+
+        JumpTable { width, stops }
+    }
+
+    /// Where the guard stops when walking from `state`, as if there were also an obstacle at
+    /// `extra`.
+    pub fn stop(&self, state: State, extra: Pos) -> Option<Pos> {
+        let stop = self.stops[state_index(self.width, state.pos, state.dir)];
+
+        // how far along the line from `state.pos` is `pos`, if it's ahead at all
+        let ahead = |pos: Pos| {
+            let (along, across) = match state.dir {
+                Dir::Up => (state.pos.row as isize - pos.row as isize, pos.col == state.pos.col),
+                Dir::Down => (pos.row as isize - state.pos.row as isize, pos.col == state.pos.col),
+                Dir::Left => (state.pos.col as isize - pos.col as isize, pos.row == state.pos.row),
+                Dir::Right => (pos.col as isize - state.pos.col as isize, pos.row == state.pos.row),
+            };
+            (across && along > 0).then_some(along)
+        };
+
+        match ahead(extra) {
+            Some(extra_dist) if stop.is_none_or(|stop| extra_dist <= ahead(stop).unwrap_or(0) + 1) => {
+                extra.checked_add(state.dir.reverse())
+            }
+            _ => stop,
+        }
+    }
+
+    /// Whether the guard loops when starting from `state` with an extra obstacle at `extra`.
+    /// `seen` must cover every state index of the grid.
+    pub fn loops(&self, state: State, extra: Pos, seen: &mut impl Visited<usize>) -> bool {
+        seen.clear();
+        let mut state = state;
+        loop {
+            let Some(stop) = self.stop(state, extra) else {
+                return false;
+            };
+            // only turns are recorded, a loop has to repeat one
+            state = State {
+                pos: stop,
+                dir: state.dir.turn_right(),
+            };
+            if !seen.insert(state_index(self.width, state.pos, state.dir)) {
+                return true;
+            }
+        }
+    }
+
+    /// A visited set for [`JumpTable::loops`].
+    pub fn visited(&self) -> Bitmap<fn(&usize) -> usize> {
+        Bitmap::new(self.stops.len(), |&i| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_06::walk::{GuardWalk, Outcome};
+
+    #[test]
+    fn test_matches_walk() {
+        let mut random = crate::tests::random(0x9e3779b97f4a7c15);
+
+        let mut loops = 0;
+        for _ in 0..20 {
+            let mut grid = Grid::from_fn(40, 30, |_| match random(8) {
+                0 => Point::Full,
+                _ => Point::Empty,
+            });
+            let start = State {
+                pos: Pos::new(random(30) as usize, random(40) as usize),
+                dir: Dir::ALL[random(4) as usize],
+            };
+            grid[start.pos] = Point::Empty;
+
+            let jumps = JumpTable::new(&grid);
+            let mut seen = jumps.visited();
+            let path: Vec<_> = GuardWalk::new(&grid, start).collect();
+            for (&state, next) in path.iter().zip(&path[1..]) {
+                if next.pos == state.pos {
+                    continue;
+                }
+                let mut patched = grid.clone();
+                patched[next.pos] = Point::Full;
+                let expected = matches!(
+                    GuardWalk::new(&patched, state).run(),
+                    Outcome::Loop { .. }
+                );
+                assert_eq!(jumps.loops(state, next.pos, &mut seen), expected);
+                loops += expected as u32;
+            }
+        }
+        assert!(loops > 0);
+    }
+}
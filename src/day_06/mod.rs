@@ -1,199 +1,189 @@
-use fxhash::FxHashSet;
+use std::{fmt::Display, io};
 
-type Pos = (isize, isize);
-type Dir = (i8, i8);
+use itertools::Itertools;
+
+use crate::{
+    grid::{Dir, Grid, Pos},
+    parse::ParseError,
+    Solution, Visualization,
+};
+
+pub mod jump;
+pub mod visualize;
+pub mod walk;
+
+use jump::JumpTable;
+use walk::{GuardWalk, Outcome, Rules, State};
 
 #[derive(Clone)]
 pub struct Input {
-    grid: Vec<Vec<Point>>,
-    start: Pos,
-    dir: Dir,
+    grid: Grid<Point>,
+    /// In the order they appear on the map
+    guards: Vec<State>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Point {
     Empty,
     Full,
 }
 
 impl Input {
-    fn get_point(&self, (row, col): Pos) -> Option<&Point> {
-        self.grid
-            .get(row as usize)
-            .and_then(|row| row.get(col as usize))
-    }
-    fn get_point_mut(&mut self, (row, col): Pos) -> Option<&mut Point> {
-        self.grid
-            .get_mut(row as usize)
-            .and_then(|row| row.get_mut(col as usize))
+    pub fn grid(&self) -> &Grid<Point> {
+        &self.grid
     }
-}
 
-fn parse_input(input: &str) -> Input {
-    let mut start = (0, 0);
-    let mut dir = None;
-    let grid = input
-        .lines()
-        .enumerate()
-        .map(|(row, line)| {
-            line.bytes()
-                .enumerate()
-                .map(|(col, b)| match b {
-                    b'#' => Point::Full,
-                    b'.' => Point::Empty,
-                    _ => {
-                        start = (row as isize, col as isize);
-                        dir = Some(match b {
-                            b'^' => (-1, 0),
-                            b'>' => (0, 1),
-                            b'v' => (1, 0),
-                            b'<' => (0, -1),
-                            _ => panic!("unexpected byte in grid: {b:x}"),
-                        });
-                        Point::Empty
-                    }
-                })
-                .collect()
-        })
-        .collect();
+    /// The first guard, the only one in the puzzle.
+    pub fn start(&self) -> State {
+        self.guards[0]
+    }
 
-    Input {
-        grid,
-        start,
-        dir: dir.expect("starting point should exist in grid"),
+    pub fn guards(&self) -> &[State] {
+        &self.guards
     }
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input(input)
-}
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let mut guards = vec![];
+    let grid = Grid::parse_with(input, |pos, b| match b {
+        b'#' => Ok(Point::Full),
+        b'.' => Ok(Point::Empty),
+        _ => {
+            let dir = Dir::from_char(b as char)
+                .ok_or_else(|| format!("unexpected byte in grid: {b:x}"))?;
+            guards.push(State { pos, dir });
+            Ok(Point::Empty)
+        }
+    })?;
 
-fn rotate((y, x): Dir) -> Dir {
-    // (x + yi) * i = (-y + xi)
-    (x, -y)
+    if guards.is_empty() {
+        return Err(ParseError::at(
+            input,
+            input.len(),
+            "starting point should exist in grid",
+        ));
+    }
+    Ok(Input { grid, guards })
 }
 
-#[allow(unused)]
-fn print(input: &Input, pos: Pos, dir: Dir) {
-    eprintln!("=======================");
-    let mut s = String::new();
-    for (y, row) in input.grid.iter().enumerate() {
-        for (x, col) in row.iter().enumerate() {
-            if (y as isize, x as isize) == pos {
-                s.push(match dir {
-                    (-1, 0) => '^',
-                    (0, 1) => '>',
-                    (1, 0) => 'v',
-                    (0, -1) => '<',
-                    _ => unreachable!(),
-                });
-            } else {
-                match col {
-                    Point::Empty => s.push('.'),
-                    Point::Full => s.push('#'),
-                }
-            }
-        }
-        s.push('\n');
-    }
-    eprintln!("{}", s);
-    eprintln!("=======================");
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    parse_input(input)
 }
 
 pub fn part_1(input: &Input) -> u32 {
-    let mut pos = input.start;
-    let mut dir = input.dir;
-
     // A hashset would work, but this is much faster
-    let mut visited_grid: Vec<Vec<bool>> = input
-        .grid
-        .iter()
-        .map(|row| vec![false; row.len()])
-        .collect();
+    let mut visited_grid = input.grid.map(|_| false);
     let mut visited_count = 0;
 
-    loop {
-        let p = &mut visited_grid[pos.0 as usize][pos.1 as usize];
+    for state in GuardWalk::new(&input.grid, input.start()) {
+        let p = &mut visited_grid[state.pos];
         if !*p {
             *p = true;
             visited_count += 1;
         }
-
-        loop {
-            let next = (pos.0 + dir.0 as isize, pos.1 + dir.1 as isize);
-            if let Some(p) = input.get_point(next) {
-                if matches!(p, Point::Full) {
-                    dir = rotate(dir);
-                    continue;
-                }
-                pos = next;
-                break;
-            } else {
-                return visited_count;
-            }
-        }
     }
+    visited_count
 }
 
-type CycleSet = FxHashSet<(Pos, Dir)>;
-
-fn is_cyclic(cycle: &mut CycleSet, input: &Input, mut pos: Pos, mut dir: Dir) -> bool {
-    cycle.clear();
-
-    loop {
-        let next = (pos.0 + dir.0 as isize, pos.1 + dir.1 as isize);
-        if let Some(p) = input.get_point(next) {
-            if matches!(p, Point::Full) {
-                dir = rotate(dir);
-                if cycle.contains(&(pos, dir)) {
-                    return true;
-                }
-                cycle.insert((pos, dir));
-            } else {
-                pos = next;
-            }
-        } else {
-            return false;
-        }
-    }
+/// Each `(state, pos)` where an obstacle could be added at `pos` in front of the guard, in the
+/// order the guard gets there. Obstacles on the path before that would have changed it, so only
+/// the first time the guard reaches each position counts.
+fn obstacle_candidates(input: &Input) -> impl Iterator<Item = (State, Pos)> {
+    let path: Vec<_> = GuardWalk::new(&input.grid, input.start()).collect();
+    let mut visited = input.grid.map(|_| false);
+    visited[input.start().pos] = true;
+
+    path.into_iter()
+        .tuple_windows()
+        .filter(move |(_, next)| !std::mem::replace(&mut visited[next.pos], true))
+        .map(|(state, next)| (state, next.pos))
+}
+
+/// How each guard's walk ends under `rules`, in the order the guards appear on the map. The
+/// guards walk independently and don't get in each other's way.
+pub fn simulate(input: &Input, rules: Rules) -> Vec<(State, Outcome)> {
+    input
+        .guards
+        .iter()
+        .map(|&guard| (guard, GuardWalk::with_rules(&input.grid, guard, rules).run()))
+        .collect()
 }
 
 pub fn part_2(input: &Input) -> u32 {
-    let mut input = input.clone();
+    let jumps = JumpTable::new(&input.grid);
+    let mut seen = jumps.visited();
 
-    let mut pos = input.start;
-    let mut dir = input.dir;
+    // Pretend there was a barrier in front, would we then enter a cycle?
+    obstacle_candidates(input)
+        .filter(|&(state, pos)| jumps.loops(state, pos, &mut seen))
+        .count() as u32
+}
 
-    let mut new_barriers = FxHashSet::default();
-    let mut path = FxHashSet::default();
+/// An obstacle that traps the guard in a loop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoopObstacle {
+    pub pos: Pos,
+    /// Where the guard is when it first runs into the obstacle
+    pub from: State,
+    /// The first state of the walk that's part of the loop
+    pub entry: State,
+    pub len: usize,
+}
 
-    let mut cycle = FxHashSet::default();
+/// Every obstacle counted by part 2 along with the loop it causes, sorted by position.
+pub fn loop_obstacles(input: &Input) -> Vec<LoopObstacle> {
+    let jumps = JumpTable::new(&input.grid);
+    let mut seen = jumps.visited();
+    let mut grid = input.grid.clone();
 
-    loop {
-        path.insert(pos);
+    let mut obstacles: Vec<_> = obstacle_candidates(input)
+        .filter(|&(state, pos)| jumps.loops(state, pos, &mut seen))
+        .map(|(from, pos)| {
+            grid[pos] = Point::Full;
+            let outcome = GuardWalk::new(&grid, input.start()).run();
+            grid[pos] = Point::Empty;
 
-        let next = (pos.0 + dir.0 as isize, pos.1 + dir.1 as isize);
-        if let Some(p) = input.get_point_mut(next) {
-            if matches!(p, Point::Full) {
-                dir = rotate(dir);
-                continue;
+            let Outcome::Loop { start, len } = outcome else {
+                unreachable!("the jump table found a loop");
+            };
+            LoopObstacle {
+                pos,
+                from,
+                entry: start,
+                len,
             }
+        })
+        .collect();
+    obstacles.sort_unstable_by_key(|o| o.pos);
+    obstacles
+}
 
-            // Pretend there was a barrier, would we then enter a cycle?
-            // Make sure to not try placing a barrier on part of the path already completed.
-            *p = Point::Full;
-            if !path.contains(&next)
-                && !new_barriers.contains(&next)
-                && is_cyclic(&mut cycle, &input, pos, rotate(dir))
-            {
-                new_barriers.insert(next);
-            }
-            *input.get_point_mut(next).unwrap() = Point::Empty;
+pub struct Day6;
 
-            pos = next;
-        } else {
-            return new_barriers.len() as u32;
-        }
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Input;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input_generator(input)
+    }
+
+    fn part_1(input: &Self::Input<'_>) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> impl Display {
+        part_2(input)
+    }
+
+    fn visualize(
+        input: &Self::Input<'_>,
+        part: u8,
+        style: Visualization,
+        out: &mut dyn io::Write,
+    ) -> Option<io::Result<()>> {
+        Some(visualize::visualize(input, part, style, out))
     }
 }
 
@@ -217,14 +207,92 @@ mod tests {
             #.........
             ......#...
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 41);
         assert_eq!(part_2(&input), 6);
+
+        let obstacles = loop_obstacles(&input);
+        assert_eq!(
+            obstacles.iter().map(|o| o.pos).collect::<Vec<_>>(),
+            [
+                Pos::new(6, 3),
+                Pos::new(7, 6),
+                Pos::new(7, 7),
+                Pos::new(8, 1),
+                Pos::new(8, 3),
+                Pos::new(9, 7),
+            ]
+        );
+        assert_eq!(obstacles[0].len, 22);
+        assert_eq!(
+            obstacles[0].from,
+            State {
+                pos: Pos::new(6, 4),
+                dir: Dir::Left
+            }
+        );
+        assert!(visualize::render_obstacles(&input, &obstacles).starts_with("6 obstacles\n"));
+    }
+
+    #[test]
+    fn test_simulate() {
+        use walk::{Edges, TurnPolicy};
+
+        let input = input_generator(".#...\n....#\n.^...\n#..<.\n...v.\n").unwrap();
+        let outcomes = |turn, edges| {
+            simulate(&input, Rules { turn, edges })
+                .into_iter()
+                .map(|(_, outcome)| outcome)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            simulate(&input, Rules::default())
+                .iter()
+                .map(|(guard, _)| (guard.pos, guard.dir))
+                .collect::<Vec<_>>(),
+            [
+                (Pos::new(2, 1), Dir::Up),
+                (Pos::new(3, 3), Dir::Left),
+                (Pos::new(4, 3), Dir::Down),
+            ]
+        );
+        assert_eq!(
+            outcomes(TurnPolicy::Right, Edges::Exit),
+            [Outcome::Exit; 3]
+        );
+        assert_eq!(
+            outcomes(TurnPolicy::Reverse, Edges::Exit),
+            [Outcome::Exit; 3]
+        );
+
+        // the last guard goes round column 3 without ever turning
+        assert_eq!(
+            outcomes(TurnPolicy::Right, Edges::Wrap)[2],
+            Outcome::Loop {
+                start: State {
+                    pos: Pos::new(4, 3),
+                    dir: Dir::Down
+                },
+                len: 5
+            }
+        );
+        // bouncing between the obstacle at the top of column 1 and itself, seen from below
+        assert_eq!(
+            outcomes(TurnPolicy::Reverse, Edges::Wrap)[0],
+            Outcome::Loop {
+                start: State {
+                    pos: Pos::new(2, 1),
+                    dir: Dir::Up
+                },
+                len: 8
+            }
+        );
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day6.txt"));
+        let input = input_generator(include_str!("../../input/2024/day6.txt")).unwrap();
         assert_eq!(part_1(&input), 5129);
         assert_eq!(part_2(&input), 1888);
     }
@@ -0,0 +1,233 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use itertools::Itertools;
+
+use super::{
+    loop_obstacles,
+    walk::{step, GuardWalk, Outcome, State},
+    Input, LoopObstacle, Point,
+};
+use crate::{
+    grid::{Grid, Pos},
+    Visualization,
+};
+
+const FRAME_DELAY: Duration = Duration::from_millis(40);
+
+/// What to draw on a plot, later variants draw over earlier ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Mark {
+    None,
+    Visited,
+    Turn,
+    Loop,
+    Obstacle,
+}
+
+impl Mark {
+    fn symbol(self) -> Option<(char, &'static str)> {
+        match self {
+            Mark::None => None,
+            Mark::Visited => Some(('X', "33")),
+            Mark::Turn => Some(('+', "31")),
+            Mark::Loop => Some(('@', "32")),
+            Mark::Obstacle => Some(('O', "35")),
+        }
+    }
+}
+
+fn mark(marks: &mut Grid<Mark>, pos: Pos, mark: Mark) {
+    marks[pos] = marks[pos].max(mark);
+}
+
+fn draw(
+    out: &mut dyn Write,
+    style: Visualization,
+    input: &Input,
+    marks: &Grid<Mark>,
+    guard: Option<State>,
+    caption: &str,
+) -> io::Result<()> {
+    let colour = style == Visualization::Animate;
+    if colour {
+        // home the cursor and clear the screen
+        write!(out, "\x1b[H\x1b[2J")?;
+    }
+    writeln!(out, "{caption}")?;
+
+    for (pos, point) in input.grid.iter() {
+        match (guard.filter(|g| g.pos == pos), marks[pos].symbol()) {
+            (Some(guard), _) if colour => write!(out, "\x1b[1m{}\x1b[0m", guard.dir.to_char())?,
+            (Some(guard), _) => write!(out, "{}", guard.dir.to_char())?,
+            (None, Some((c, code))) if colour => write!(out, "\x1b[{code}m{c}\x1b[0m")?,
+            (None, Some((c, _))) => write!(out, "{c}")?,
+            (None, None) => write!(
+                out,
+                "{}",
+                match point {
+                    Point::Full => '#',
+                    Point::Empty => '.',
+                }
+            )?,
+        }
+        if pos.col + 1 == input.grid.width() {
+            writeln!(out)?;
+        }
+    }
+
+    if colour {
+        out.flush()?;
+        thread::sleep(FRAME_DELAY);
+    } else {
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Part 1 draws a frame at every turn of the walk, part 2 draws one for each obstacle that
+/// would trap the guard in a loop.
+pub fn visualize(
+    input: &Input,
+    part: u8,
+    style: Visualization,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut marks = input.grid.map(|_| Mark::None);
+    let mut walk = GuardWalk::new(&input.grid, input.start());
+    let path: Vec<_> = walk.by_ref().collect();
+
+    if part == 1 {
+        let mut turns = 0;
+        mark(&mut marks, path[0].pos, Mark::Visited);
+        for (i, (prev, state)) in path.iter().tuple_windows().enumerate() {
+            mark(&mut marks, state.pos, Mark::Visited);
+            if prev.dir != state.dir {
+                turns += 1;
+                mark(&mut marks, state.pos, Mark::Turn);
+                let caption = format!("step {}, turn {turns}", i + 1);
+                draw(out, style, input, &marks, Some(*state), &caption)?;
+            }
+        }
+
+        let visited = marks.iter().filter(|(_, &m)| m != Mark::None).count();
+        let caption = match walk.outcome() {
+            Some(Outcome::Loop { len, .. }) => format!("stuck in a loop of {len} steps"),
+            _ => format!("left the map after {} steps, visiting {visited} positions", path.len()),
+        };
+        return draw(out, style, input, &marks, path.last().copied(), &caption);
+    }
+
+    for state in &path {
+        mark(&mut marks, state.pos, Mark::Visited);
+    }
+
+    let obstacles = loop_obstacles(input);
+    let mut grid = input.grid.clone();
+    for (i, obstacle) in obstacles.iter().enumerate() {
+        let mut frame = marks.clone();
+        grid[obstacle.pos] = Point::Full;
+        let states = std::iter::successors(Some(obstacle.entry), |&s| step(&grid, s));
+        for s in states.take(obstacle.len) {
+            mark(&mut frame, s.pos, Mark::Loop);
+        }
+        grid[obstacle.pos] = Point::Empty;
+        mark(&mut frame, obstacle.pos, Mark::Obstacle);
+
+        let caption = format!(
+            "obstacle {} at {:?}: a loop of {} steps",
+            i + 1,
+            (obstacle.pos.row, obstacle.pos.col),
+            obstacle.len
+        );
+        draw(out, style, input, &frame, Some(obstacle.from), &caption)?;
+    }
+
+    for obstacle in &obstacles {
+        mark(&mut marks, obstacle.pos, Mark::Obstacle);
+    }
+    let caption = format!("{} obstacles", obstacles.len());
+    draw(out, style, input, &marks, None, &caption)
+}
+
+/// The map with the guard's path and `obstacles` marked, as plain text.
+pub fn render_obstacles(input: &Input, obstacles: &[LoopObstacle]) -> String {
+    let mut marks = input.grid.map(|_| Mark::None);
+    for state in GuardWalk::new(&input.grid, input.start()) {
+        mark(&mut marks, state.pos, Mark::Visited);
+    }
+    for obstacle in obstacles {
+        mark(&mut marks, obstacle.pos, Mark::Obstacle);
+    }
+
+    let mut out = vec![];
+    draw(
+        &mut out,
+        Visualization::Frames,
+        input,
+        &marks,
+        Some(input.start()),
+        &format!("{} obstacles", obstacles.len()),
+    )
+    .expect("writing to a Vec can't fail");
+    String::from_utf8(out).expect("the map is ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_06::input_generator;
+    use indoc::indoc;
+
+    #[test]
+    fn test_frames() {
+        let input = input_generator(".#..\n....\n.^..\n").unwrap();
+
+        let mut out = vec![];
+        visualize(&input, 1, Visualization::Frames, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "step 2, turn 1\n.#..\n.>..\n.X..\n\n\
+             left the map after 5 steps, visiting 4 positions\n.#..\n.+X>\n.X..\n\n"
+        );
+
+        let input = input_generator(indoc! {
+            "
+            ....#.....
+            .........#
+            ..........
+            ..#.......
+            .......#..
+            ..........
+            .#..^.....
+            ........#.
+            #.........
+            ......#...
+            "
+        })
+        .unwrap();
+        let mut out = vec![];
+        visualize(&input, 2, Visualization::Frames, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("obstacle ").count(), 6);
+        assert!(out.contains("6 obstacles\n"));
+        assert!(out.starts_with(indoc! {
+            "
+            obstacle 1 at (6, 3): a loop of 22 steps
+            ....#.....
+            ....@@@@@#
+            ....@...@.
+            ..#.@...@.
+            ..XX@XX#@.
+            ..X.@.X.@.
+            .#XO<@@@@.
+            .XXXXXXX#.
+            #XXXXXXX..
+            ......#X..
+            "
+        }), "{out}");
+    }
+}
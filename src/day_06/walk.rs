@@ -0,0 +1,199 @@
+use fxhash::FxHashMap;
+
+use super::Point;
+use crate::grid::{Dir, Grid, Pos};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct State {
+    pub pos: Pos,
+    pub dir: Dir,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Exit,
+    /// `start` is the first state of the walk that's part of the loop
+    Loop { start: State, len: usize },
+}
+
+/// What the guard does when the way ahead is blocked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TurnPolicy {
+    #[default]
+    Right,
+    Left,
+    Reverse,
+}
+
+impl TurnPolicy {
+    pub fn turn(self, dir: Dir) -> Dir {
+        match self {
+            TurnPolicy::Right => dir.turn_right(),
+            TurnPolicy::Left => dir.turn_left(),
+            TurnPolicy::Reverse => dir.reverse(),
+        }
+    }
+}
+
+/// What happens when the guard walks off the grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Edges {
+    /// The guard leaves and the walk is over
+    #[default]
+    Exit,
+    /// The guard comes back in on the opposite edge
+    Wrap,
+}
+
+/// The rules of the walk, the defaults are the puzzle's.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    pub turn: TurnPolicy,
+    pub edges: Edges,
+}
+
+/// The state after `state`: a step forward, or a turn if that's blocked. `None` once the guard
+/// walks off the grid.
+pub fn step(grid: &Grid<Point>, state: State) -> Option<State> {
+    step_with(grid, state, Rules::default())
+}
+
+pub fn step_with(grid: &Grid<Point>, state: State, rules: Rules) -> Option<State> {
+    let next = match (grid.offset(state.pos, state.dir), rules.edges) {
+        (Some(next), _) => next,
+        (None, Edges::Exit) => return None,
+        (None, Edges::Wrap) => grid.wrapping_offset(state.pos, state.dir),
+    };
+    Some(match grid[next] {
+        Point::Full => State {
+            pos: state.pos,
+            dir: rules.turn.turn(state.dir),
+        },
+        Point::Empty => State {
+            pos: next,
+            dir: state.dir,
+        },
+    })
+}
+
+/// Yields each state of the guard's walk, starting with `start`. The walk stops once the guard
+/// leaves the grid or is about to repeat a turn, at which point [`GuardWalk::outcome`] says which.
+pub struct GuardWalk<'a> {
+    grid: &'a Grid<Point>,
+    rules: Rules,
+    start: State,
+    next: Option<State>,
+    steps: usize,
+    /// The step at which each turn (or wrap around an edge) was made. Every loop has one of
+    /// those, so it's enough to watch for a repeated turn rather than hashing every state.
+    turns: FxHashMap<State, usize>,
+    outcome: Option<Outcome>,
+}
+
+impl<'a> GuardWalk<'a> {
+    pub fn new(grid: &'a Grid<Point>, start: State) -> Self {
+        Self::with_rules(grid, start, Rules::default())
+    }
+
+    pub fn with_rules(grid: &'a Grid<Point>, start: State, rules: Rules) -> Self {
+        GuardWalk {
+            grid,
+            rules,
+            start,
+            next: Some(start),
+            steps: 0,
+            turns: FxHashMap::default(),
+            outcome: None,
+        }
+    }
+
+    /// `None` until the walk is over.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Walks to the end and returns the outcome.
+    pub fn run(mut self) -> Outcome {
+        self.by_ref().for_each(drop);
+        self.outcome.expect("the walk is over")
+    }
+
+    /// The first state that's `len` steps before a repeat of itself.
+    fn loop_start(&self, len: usize) -> State {
+        let states = || std::iter::successors(Some(self.start), |&s| step_with(self.grid, s, self.rules));
+        states()
+            .zip(states().skip(len))
+            .find(|(a, b)| a == b)
+            .expect("the walk loops")
+            .0
+    }
+}
+
+impl Iterator for GuardWalk<'_> {
+    type Item = State;
+
+    fn next(&mut self) -> Option<State> {
+        let state = self.next.take()?;
+        self.steps += 1;
+
+        let wraps = self.grid.offset(state.pos, state.dir).is_none();
+        match step_with(self.grid, state, self.rules) {
+            None => self.outcome = Some(Outcome::Exit),
+            Some(next) if next.dir != state.dir || wraps => match self.turns.get(&next) {
+                Some(&step) => {
+                    let len = self.steps - step;
+                    self.outcome = Some(Outcome::Loop {
+                        start: self.loop_start(len),
+                        len,
+                    });
+                }
+                None => {
+                    self.turns.insert(next, self.steps);
+                    self.next = Some(next);
+                }
+            },
+            Some(next) => self.next = Some(next),
+        }
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_06::input_generator;
+
+    #[test]
+    fn test_walk() {
+        let input = input_generator(".#..\n...#\n#...\n.^#.\n").unwrap();
+        let mut walk = GuardWalk::new(&input.grid, input.start());
+        let states: Vec<_> = walk.by_ref().take(4).collect();
+        assert_eq!(
+            states.iter().map(|s| (s.pos, s.dir)).collect::<Vec<_>>(),
+            [
+                (Pos::new(3, 1), Dir::Up),
+                (Pos::new(2, 1), Dir::Up),
+                (Pos::new(1, 1), Dir::Up),
+                (Pos::new(1, 1), Dir::Right),
+            ]
+        );
+        assert_eq!(walk.outcome(), None);
+
+        // right to (1, 2), down to (2, 2), left to (2, 1), up to (1, 1) and round again, which
+        // the guard joined on its first step
+        assert_eq!(
+            walk.run(),
+            Outcome::Loop {
+                start: State {
+                    pos: Pos::new(2, 1),
+                    dir: Dir::Up
+                },
+                len: 8
+            }
+        );
+
+        let input = input_generator("....\n.^..\n").unwrap();
+        let walk = GuardWalk::new(&input.grid, input.start());
+        assert_eq!(walk.run(), Outcome::Exit);
+    }
+}
@@ -5,6 +5,8 @@ use winnow::{
     Parser,
 };
 
+use crate::parse::ParseError;
+
 pub struct Input {
     equations: Vec<Eq>,
 }
@@ -25,8 +27,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     .parse_next(input)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 // By working backwards from the end we are able to prune the search tree because of divisibility constraints.
@@ -81,6 +83,8 @@ pub fn part_2(input: &Input) -> i64 {
     )
 }
 
+solution!(Day7, 7, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,14 +114,14 @@ mod tests {
             21037: 9 7 18 13
             292: 11 6 16 20
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 3749);
         assert_eq!(part_2(&input), 11387);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day7.txt"));
+        let input = input_generator(include_str!("../../input/2024/day7.txt")).unwrap();
         assert_eq!(part_1(&input), 882304362421);
         assert_eq!(part_2(&input), 145149066755184);
     }
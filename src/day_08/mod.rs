@@ -1,103 +1,83 @@
 use gcd::Gcd;
 
-pub struct Input<'a> {
-    data: Vec<&'a [u8]>,
+use crate::{
+    grid::{Grid, Pos, Vec2},
+    parse::ParseError,
+};
+
+pub type Input = Grid<u8>;
+
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    Input {
-        data: input.lines().map(|s| s.as_bytes()).collect(),
-    }
+fn antennas(input: &Input) -> impl Iterator<Item = (Pos, Pos)> + '_ {
+    input
+        .iter()
+        .filter(|(_, col)| matches!(col, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9'))
+        .flat_map(move |(pos, &col)| {
+            input
+                .iter()
+                .filter(move |&(pos2, &col2)| col2 == col && pos2 != pos)
+                .map(move |(pos2, _)| (pos, pos2))
+        })
+}
+
+#[cfg(debug_assertions)]
+fn print(antinode_map: &Grid<u8>) {
+    antinode_map
+        .rows()
+        .for_each(|row| eprintln!("{}", std::str::from_utf8(row).unwrap()));
 }
 
 pub fn part_1(input: &Input) -> u32 {
-    let mut antinode_map = input.data.iter().map(|r| r.to_vec()).collect::<Vec<_>>();
+    let mut antinode_map = input.clone();
 
     let mut count = 0;
 
-    for (y, row) in input.data.iter().enumerate() {
-        for (x, &col) in row.iter().enumerate() {
-            if matches!(col,
-                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9')
-            {
-                for (y2, row2) in input.data.iter().enumerate() {
-                    for (x2, &col2) in row2.iter().enumerate() {
-                        if col2 == col && (y2, x2) != (y, x) {
-                            let (y3, x3) =
-                                (2 * y2 as isize - y as isize, 2 * x2 as isize - x as isize);
-
-                            if let Some(col) = antinode_map
-                                .get_mut(y3 as usize)
-                                .and_then(|row| row.get_mut(x3 as usize))
-                            {
-                                if *col != b'#' {
-                                    *col = b'#';
-                                    count += 1;
-                                }
-                            }
-                        }
-                    }
-                }
+    for (pos, pos2) in antennas(input) {
+        if let Some(pos3) = input.offset(pos2, pos.delta_to(pos2)) {
+            let col = &mut antinode_map[pos3];
+            if *col != b'#' {
+                *col = b'#';
+                count += 1;
             }
         }
     }
 
     #[cfg(debug_assertions)]
-    {
-        antinode_map
-            .iter()
-            .for_each(|row| eprintln!("{}", std::str::from_utf8(row).unwrap()));
-    }
+    print(&antinode_map);
 
     count
 }
 
 pub fn part_2(input: &Input) -> u32 {
-    let mut antinode_map = input.data.iter().map(|r| r.to_vec()).collect::<Vec<_>>();
+    let mut antinode_map = input.clone();
 
     let mut count = 0;
 
-    for (y, row) in input.data.iter().enumerate() {
-        for (x, &col) in row.iter().enumerate() {
-            if matches!(col,
-                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9')
-            {
-                for (y2, row2) in input.data.iter().enumerate() {
-                    for (x2, &col2) in row2.iter().enumerate() {
-                        if col2 == col && (y2, x2) != (y, x) {
-                            let (dy, dx) = (y2 as isize - y as isize, x2 as isize - x as isize);
-                            let gcd = dy.unsigned_abs().gcd(dx.unsigned_abs()) as isize;
-                            let (dy, dx) = (dy / gcd, dx / gcd);
-
-                            let (mut y3, mut x3) = (y2 as isize, x2 as isize);
-                            while let Some(col) = antinode_map
-                                .get_mut(y3 as usize)
-                                .and_then(|row| row.get_mut(x3 as usize))
-                            {
-                                if *col != b'#' {
-                                    *col = b'#';
-                                    count += 1;
-                                }
-                                y3 += dy;
-                                x3 += dx;
-                            }
-                        }
-                    }
-                }
+    for (pos, pos2) in antennas(input) {
+        let delta = pos.delta_to(pos2);
+        let gcd = delta.row.unsigned_abs().gcd(delta.col.unsigned_abs()) as isize;
+        let delta = Vec2::new(delta.row / gcd, delta.col / gcd);
+
+        for pos3 in std::iter::once(pos2).chain(input.ray(pos2, delta)) {
+            let col = &mut antinode_map[pos3];
+            if *col != b'#' {
+                *col = b'#';
+                count += 1;
             }
         }
     }
 
     #[cfg(debug_assertions)]
-    {
-        antinode_map
-            .iter()
-            .for_each(|row| eprintln!("{}", std::str::from_utf8(row).unwrap()));
-    }
+    print(&antinode_map);
 
     count
 }
 
+solution!(Day8, 8, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,14 +101,14 @@ mod tests {
             ............
             "
 
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 14);
         assert_eq!(part_2(&input), 34);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day8.txt"));
+        let input = input_generator(include_str!("../../input/2024/day8.txt")).unwrap();
         assert_eq!(part_1(&input), 318);
         assert_eq!(part_2(&input), 1126);
     }
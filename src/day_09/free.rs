@@ -0,0 +1,91 @@
+use super::Extent;
+
+/// The free spans of a disk in order, indexed so the leftmost span that fits a file can be found
+/// in logarithmic time whatever the lengths involved.
+pub struct FreeSpans {
+    spans: Vec<Extent>,
+    leaves: usize,
+    /// A segment tree of the longest span under each node, rooted at 1 with the spans as leaves
+    /// from `leaves` onwards
+    longest: Vec<u64>,
+}
+
+impl FreeSpans {
+    pub fn new(spans: Vec<Extent>) -> Self {
+        let leaves = spans.len().next_power_of_two();
+        let mut longest = vec![0; 2 * leaves];
+        for (i, span) in spans.iter().enumerate() {
+            longest[leaves + i] = span.len;
+        }
+        for i in (1..leaves).rev() {
+            longest[i] = longest[2 * i].max(longest[2 * i + 1]);
+        }
+        FreeSpans {
+            spans,
+            leaves,
+            longest,
+        }
+    }
+
+    pub fn spans(&self) -> &[Extent] {
+        &self.spans
+    }
+
+    /// The index of the leftmost span at least `len` long.
+    pub fn first_fit(&self, len: u64) -> Option<usize> {
+        if self.spans.is_empty() || self.longest[1] < len {
+            return None;
+        }
+        let mut node = 1;
+        while node < self.leaves {
+            node = if self.longest[2 * node] >= len {
+                2 * node
+            } else {
+                2 * node + 1
+            };
+        }
+        Some(node - self.leaves)
+    }
+
+    /// Takes `len` blocks from the start of span `index` and returns them.
+    pub fn take(&mut self, index: usize, len: u64) -> Extent {
+        let span = &mut self.spans[index];
+        assert!(span.len >= len, "{span:?} is shorter than {len}");
+        let taken = Extent {
+            start: span.start,
+            len,
+        };
+        span.start += len;
+        span.len -= len;
+
+        let mut node = self.leaves + index;
+        self.longest[node] = span.len;
+        while node > 1 {
+            node /= 2;
+            self.longest[node] = self.longest[2 * node].max(self.longest[2 * node + 1]);
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_spans() {
+        let extent = |start, len| Extent { start, len };
+        let mut free = FreeSpans::new(vec![extent(1, 3), extent(6, 1), extent(9, 12)]);
+        assert_eq!(free.first_fit(1), Some(0));
+        assert_eq!(free.first_fit(4), Some(2));
+        assert_eq!(free.first_fit(13), None);
+
+        assert_eq!(free.take(0, 3), extent(1, 3));
+        assert_eq!(free.first_fit(1), Some(1));
+        assert_eq!(free.take(2, 10), extent(9, 10));
+        assert_eq!(free.first_fit(2), Some(2));
+        assert_eq!(free.spans()[2], extent(19, 2));
+
+        assert_eq!(FreeSpans::new(vec![]).first_fit(0), None);
+    }
+}
@@ -1,123 +1,203 @@
-const EMPTY_ID: i16 = -1;
+use crate::parse::ParseError;
 
-#[derive(Clone)]
-pub struct Input {
-    data: Vec<i16>,
+pub mod free;
+
+use free::FreeSpans;
+
+/// A run of blocks on the disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extent {
+    pub start: u64,
+    pub len: u64,
 }
 
-impl Input {
-    fn checksum(&self) -> u64 {
-        self.data
-            .iter()
-            .enumerate()
-            .filter(|(_pos, id)| **id != EMPTY_ID)
-            .map(|(pos, id)| pos as u64 * *id as u64)
-            .sum()
+impl Extent {
+    /// The checksum of the file `id` stored in this extent, without going block by block.
+    fn checksum(self, id: u64) -> u64 {
+        // id * (start + (start + 1) + ... + (start + len - 1))
+        id * (self.len * self.start + self.len * self.len.saturating_sub(1) / 2)
     }
 }
 
-pub fn input_generator(input: &str) -> Input {
-    let mut data = Vec::with_capacity(input.len() * 5);
-    let mut input = input.trim_end().bytes();
-    let mut id = 0i16;
-    while let Some(count) = input.next() {
-        match count {
-            b'0'..=b'9' => data.extend(std::iter::repeat_n(id, (count - b'0') as usize)),
-            _ => panic!("unexpected byte: {count}"),
-        }
-        id = id.checked_add(1).expect("ID should not overflow");
-        if let Some(count) = input.next() {
-            match count {
-                b'0'..=b'9' => data.extend(std::iter::repeat_n(EMPTY_ID, (count - b'0') as usize)),
-                _ => panic!("unexpected byte: {count}"),
+#[derive(Clone)]
+pub struct Input {
+    /// Indexed by file ID
+    files: Vec<Extent>,
+    /// The runs of free space between the files, in order
+    gaps: Vec<Extent>,
+}
+
+impl Input {
+    /// Lays out a disk from the alternating lengths of files and free space, starting with a
+    /// file.
+    pub fn from_lengths(lengths: impl IntoIterator<Item = u64>) -> Self {
+        let mut input = Input {
+            files: vec![],
+            gaps: vec![],
+        };
+        let mut start = 0;
+        for (i, len) in lengths.into_iter().enumerate() {
+            let extent = Extent { start, len };
+            if i % 2 == 0 {
+                input.files.push(extent);
+            } else if len > 0 {
+                // free space either side of an empty file is one gap
+                match input.gaps.last_mut() {
+                    Some(gap) if gap.start + gap.len == start => gap.len += len,
+                    _ => input.gaps.push(extent),
+                }
             }
+            start += len;
         }
+        input
+    }
+
+    pub fn files(&self) -> &[Extent] {
+        &self.files
     }
-    Input { data }
+
+    pub fn gaps(&self) -> &[Extent] {
+        &self.gaps
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    let lengths = input
+        .trim_end()
+        .bytes()
+        .enumerate()
+        .map(|(i, b)| match b {
+            b'0'..=b'9' => Ok((b - b'0') as u64),
+            _ => Err(ParseError::at(input, i, format!("unexpected byte: {b}"))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Input::from_lengths(lengths))
 }
 
 pub fn part_1(input: &Input) -> u64 {
-    let mut input = input.clone();
-
-    let mut left = 0;
-    let mut right = input.data.len() - 1;
-    while left < right {
-        if input.data[right] != EMPTY_ID {
-            if input.data[left] == EMPTY_ID {
-                input.data.swap(left, right);
-                left += 1;
-                right -= 1;
-            } else {
-                left += 1;
+    let mut gaps = input.gaps.iter().copied();
+    let mut gap = gaps.next();
+    let mut checksum = 0;
+
+    for (id, &file) in input.files.iter().enumerate().rev() {
+        let mut file = file;
+        // move blocks from the end of the file into the leftmost free space
+        while let Some(free) = gap.as_mut().filter(|g| g.start < file.start && file.len > 0) {
+            let moved = free.len.min(file.len);
+            checksum += Extent {
+                start: free.start,
+                len: moved,
+            }
+            .checksum(id as u64);
+            free.start += moved;
+            free.len -= moved;
+            file.len -= moved;
+            if free.len == 0 {
+                gap = gaps.next();
             }
-        } else {
-            right -= 1;
         }
+        checksum += file.checksum(id as u64);
     }
 
-    input.checksum()
+    checksum
 }
 
 pub fn part_2(input: &Input) -> u64 {
-    let mut input = input.clone();
+    let mut free = FreeSpans::new(input.gaps.clone());
+
+    let mut checksum = 0;
+    for (id, &file) in input.files.iter().enumerate().rev() {
+        let file = match free.first_fit(file.len) {
+            Some(i) if free.spans()[i].start < file.start => free.take(i, file.len),
+            _ => file,
+        };
+        checksum += file.checksum(id as u64);
+    }
 
-    // (ab)using the fact that 10 is the maximum size
-    let mut last_pos = [0usize; 10];
+    checksum
+}
 
-    let mut right = input.data.len() - 1;
-    while (0..input.data.len()).contains(&right) {
-        let id = input.data[right];
-        if id == EMPTY_ID {
-            right = right.wrapping_sub(1);
-            continue;
-        }
+solution!(Day9, 9, Input);
 
-        let mut group_size = 1;
-        while input.data.get(right.wrapping_sub(1)) == Some(&id) {
-            group_size += 1;
-            right = right.wrapping_sub(1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() {
+        let input = input_generator("2333133121414131402").unwrap();
+        assert_eq!(part_1(&input), 1928);
+        assert_eq!(part_2(&input), 2858);
+    }
+
+    #[test]
+    fn test_extents() {
+        // more files than fit in an i16, none of which move
+        let n = 100_000u64;
+        let input = Input::from_lengths((0..2 * n).map(|i| 1 - i % 2));
+        let expected = (n - 1) * n * (2 * n - 1) / 6;
+        assert_eq!(part_1(&input), expected);
+        assert_eq!(part_2(&input), expected);
+
+        // a gap far too big to lay out block by block
+        let input = Input::from_lengths([2, 1_000_000_000_000, 3]);
+        assert_eq!(input.files()[1].start, 1_000_000_000_002);
+        assert_eq!(part_1(&input), 2 + 3 + 4);
+        assert_eq!(part_2(&input), 2 + 3 + 4);
+    }
+
+    /// Compacts the disk a block at a time, the way the puzzle describes it.
+    fn compact_blocks(input: &Input, whole_files: bool) -> u64 {
+        let end = input.files().iter().map(|f| f.start + f.len).max().unwrap_or(0);
+        let mut blocks = vec![None; end as usize];
+        for (id, file) in input.files().iter().enumerate() {
+            blocks[file.start as usize..(file.start + file.len) as usize].fill(Some(id as u64));
         }
 
-        let mut left = last_pos[group_size];
-        let mut empty_size = 0;
-        while left < right {
-            if input.data[left] == EMPTY_ID {
-                empty_size += 1;
-                if empty_size == group_size {
-                    // found large enough spot, move the block
-                    let left_start = left + 1 - group_size;
-                    for i in 0..group_size {
-                        input.data.swap(right + i, left_start + i);
-                    }
-                    last_pos[group_size] = left;
+        for (id, file) in input.files().iter().enumerate().rev() {
+            let (start, len) = (file.start as usize, file.len as usize);
+            while let Some(block) = blocks[..start + len]
+                .iter()
+                .rposition(|&b| b == Some(id as u64))
+            {
+                let size = if whole_files { len } else { 1 };
+                let Some(free) = (0..block + 1 - size)
+                    .find(|&i| blocks[i..i + size].iter().all(Option::is_none))
+                else {
+                    break;
+                };
+                for i in 0..size {
+                    blocks.swap(free + i, block + 1 - size + i);
+                }
+                if whole_files {
                     break;
                 }
-            } else {
-                empty_size = 0;
             }
-            left += 1;
         }
 
-        right = right.wrapping_sub(1);
+        blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, id)| id.map(|id| pos as u64 * id))
+            .sum()
     }
 
-    input.checksum()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn test() {
-        let input = input_generator("2333133121414131402");
-        assert_eq!(part_1(&input), 1928);
-        assert_eq!(part_2(&input), 2858);
+    fn test_against_blocks() {
+        let mut random = crate::tests::random(0x853c49e6748fea9b);
+
+        for _ in 0..200 {
+            // lengths well past a single digit
+            let count = 1 + random(40);
+            let input = Input::from_lengths((0..count).map(|_| random(25)).collect::<Vec<_>>());
+            assert_eq!(part_1(&input), compact_blocks(&input, false));
+            assert_eq!(part_2(&input), compact_blocks(&input, true));
+        }
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day9.txt"));
+        let input = input_generator(include_str!("../../input/2024/day9.txt")).unwrap();
         assert_eq!(part_1(&input), 6337921897505);
         assert_eq!(part_2(&input), 6362722604045);
     }
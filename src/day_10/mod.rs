@@ -1,71 +1,47 @@
-use fxhash::FxHashSet;
+use crate::{
+    grid::{Grid, Pos},
+    parse::ParseError,
+    search::{self, Visited},
+};
 
-pub type Input<'a> = Vec<&'a [u8]>;
+pub type Input = Grid<u8>;
 
-pub fn input_generator(input: &str) -> Input {
-    input.lines().map(|line| line.as_bytes()).collect()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input)
 }
 
-fn count_trails_from(
-    (y, x): (isize, isize),
-    val: u8,
-    input: &Input,
-    reached: &mut FxHashSet<(isize, isize)>,
-    count_all_paths: bool,
-) -> u32 {
-    [(-1, 0), (0, 1), (1, 0), (0, -1)]
-        .iter()
-        .map(|&(dy, dx)| {
-            let (y2, x2) = (y + dy as isize, x + dx as isize);
-            match input.get(y2 as usize).and_then(|row| row.get(x2 as usize)) {
-                Some(&val2) if val2 == val + 1 => {
-                    if val2 == b'9' {
-                        if count_all_paths {
-                            1
-                        } else if reached.contains(&(y2, x2)) {
-                            0
-                        } else {
-                            reached.insert((y2, x2));
-                            1
-                        }
-                    } else {
-                        count_trails_from((y2, x2), val2, input, reached, count_all_paths)
-                    }
-                }
-                _ => 0,
-            }
-        })
-        .sum()
+fn uphill<'a>(input: &'a Input, pos: &Pos) -> impl Iterator<Item = Pos> + 'a {
+    let next = input[*pos] + 1;
+    input.neighbours4(*pos).filter(move |&p| input[p] == next)
 }
 
-pub fn solve(input: &Input, count_all_paths: bool) -> u32 {
-    let mut count = 0;
-    let mut reached = FxHashSet::default();
-    for (y, row) in input.iter().enumerate() {
-        for (x, col) in row.iter().enumerate() {
-            if *col == b'0' {
-                reached.clear();
-                count += count_trails_from(
-                    (y as isize, x as isize),
-                    *col,
-                    input,
-                    &mut reached,
-                    count_all_paths,
-                );
-            }
-        }
-    }
-    count
+fn trailheads(input: &Input) -> impl Iterator<Item = Pos> + '_ {
+    input
+        .iter()
+        .filter(|(_, &col)| col == b'0')
+        .map(|(pos, _)| pos)
 }
 
 pub fn part_1(input: &Input) -> u32 {
-    solve(input, false)
+    let mut visited = search::grid_bitmap(input);
+    trailheads(input)
+        .map(|start| {
+            visited.clear();
+            search::bfs([start], |p| uphill(input, p), &mut visited)
+                .filter(|(p, _)| input[*p] == b'9')
+                .count() as u32
+        })
+        .sum()
 }
 
 pub fn part_2(input: &Input) -> u32 {
-    solve(input, true)
+    trailheads(input)
+        .map(|start| search::count_paths(start, |p| uphill(input, p), |p| input[*p] == b'9') as u32)
+        .sum()
 }
 
+solution!(Day10, 10, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,14 +60,14 @@ mod tests {
             01329801
             10456732
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 36);
         assert_eq!(part_2(&input), 81);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day10.txt"));
+        let input = input_generator(include_str!("../../input/2024/day10.txt")).unwrap();
         assert_eq!(part_1(&input), 737);
         assert_eq!(part_2(&input), 1619);
     }
@@ -1,14 +1,16 @@
 use fxhash::FxHashMap;
 use winnow::{ascii::*, combinator::*, Parser};
 
+use crate::parse::ParseError;
+
 pub type Input = Vec<u64>;
 
 fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     separated(0.., dec_uint::<_, u64, _>, space1).parse_next(input)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 fn solve(input: &Input, repetitions: u8) -> u64 {
@@ -46,20 +48,22 @@ pub fn part_2(input: &Input) -> u64 {
     solve(input, 75)
 }
 
+solution!(Day11, 11, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test() {
-        let input = input_generator("125 17");
+        let input = input_generator("125 17").unwrap();
         assert_eq!(part_1(&input), 55312);
         assert_eq!(part_2(&input), 65601038650482);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day11.txt"));
+        let input = input_generator(include_str!("../../input/2024/day11.txt")).unwrap();
         assert_eq!(part_1(&input), 209412);
         assert_eq!(part_2(&input), 248967696501656);
     }
@@ -1,148 +1,167 @@
-use fxhash::FxHashSet;
+use std::fmt::Display;
 
-pub type Input<'a> = Vec<&'a [u8]>;
+use crate::{
+    grid::{Dir, Grid, Pos, Vec2},
+    parse::ParseError,
+    Day, Solution,
+};
 
-pub fn input_generator(input: &str) -> Input {
-    input.lines().map(|line| line.as_bytes()).collect()
+pub mod shape;
+
+pub type Input = Grid<u8>;
+
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Grid::parse(input)
 }
 
-fn flood_area_perimeter(
-    plant: u8,
-    input: &Input,
-    visited: &mut FxHashSet<(usize, usize)>,
-    (y, x): (usize, usize),
-) -> (u64, u64) {
-    if visited.contains(&(y, x)) {
-        return (0, 0);
-    }
-    visited.insert((y, x));
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub plant: u8,
+    pub area: u64,
+    pub perimeter: u64,
+    pub sides: u64,
+    /// Top-left corner of the bounding box
+    pub min: Pos,
+    /// Bottom-right corner of the bounding box (inclusive)
+    pub max: Pos,
+}
+
+/// How to count the sides of a region.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SideCount {
+    /// Count each run of fence along an edge once, at its first plot
+    #[default]
+    Edges,
+    /// Count the convex and concave corners of each plot, a polygon has as many sides as corners
+    Corners,
+}
+
+/// Labels each plot with the index of its region, and measures the regions.
+pub fn regions(input: &Input) -> (Grid<u32>, Vec<Region>) {
+    regions_with(input, SideCount::default())
+}
 
-    let mut area = 0;
-    let mut perimeter = 0;
+pub fn regions_with(input: &Input, side_count: SideCount) -> (Grid<u32>, Vec<Region>) {
+    let (labels, count) = input.label_regions(|a, b| a == b);
+    let mut regions = vec![None::<Region>; count as usize];
+
+    let fenced = |pos: Pos, dir: Dir| {
+        input
+            .offset(pos, dir)
+            .is_none_or(|next| labels[next] != labels[pos])
+    };
+
+    for (pos, &label) in labels.iter() {
+        let region = regions[label as usize].get_or_insert(Region {
+            plant: input[pos],
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            min: pos,
+            max: pos,
+        });
+        region.area += 1;
+        region.min.col = region.min.col.min(pos.col);
+        region.max = Pos::new(pos.row, region.max.col.max(pos.col));
 
-    if Some(&plant) == input.get(y).and_then(|row| row.get(x)) {
-        area += 1;
+        for dir in Dir::ALL {
+            if fenced(pos, dir) {
+                region.perimeter += 1;
+            }
 
-        for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            let (y2, x2) = ((y as isize + dy) as usize, (x as isize + dx) as usize);
-            match input.get(y2).and_then(|row| row.get(x2)) {
-                Some(plant2) if *plant2 == plant => {
-                    let (a2, p2) = flood_area_perimeter(plant, input, visited, (y2, x2));
-                    area += a2;
-                    perimeter += p2;
+            match side_count {
+                SideCount::Edges => {
+                    // a side is counted at its first plot, the one without the same fence just
+                    // before it
+                    let continues_side = input
+                        .offset(pos, dir.turn_left())
+                        .is_some_and(|prev| labels[prev] == label && fenced(prev, dir));
+                    if fenced(pos, dir) && !continues_side {
+                        region.sides += 1;
+                    }
+                }
+                SideCount::Corners => {
+                    // the corner between `dir` and the next direction clockwise
+                    let right = dir.turn_right();
+                    let convex = fenced(pos, dir) && fenced(pos, right);
+                    let concave = !fenced(pos, dir)
+                        && !fenced(pos, right)
+                        && input
+                            .offset(pos, Vec2::from(dir) + Vec2::from(right))
+                            .is_some_and(|diagonal| labels[diagonal] != label);
+                    if convex || concave {
+                        region.sides += 1;
+                    }
                 }
-                _ => perimeter += 1,
             }
         }
     }
 
-    (area, perimeter)
+    let regions = regions.into_iter().map(Option::unwrap).collect();
+    (labels, regions)
 }
 
 pub fn part_1(input: &Input) -> u64 {
-    let mut visited = FxHashSet::default();
-
-    let mut sum = 0;
-
-    for (y, row) in input.iter().enumerate() {
-        for (x, &col) in row.iter().enumerate() {
-            let (area, perimiter) = flood_area_perimeter(col, input, &mut visited, (y, x));
-
-            sum += area * perimiter;
-        }
-    }
-
-    sum
+    regions(input)
+        .1
+        .iter()
+        .map(|region| region.area * region.perimeter)
+        .sum()
 }
 
-fn rotate_clockwise((y, x): (isize, isize)) -> (isize, isize) {
-    (x, -y)
+pub fn part_2(input: &Input) -> u64 {
+    part_2_with(input, SideCount::default())
 }
-fn rotate_anticlockwise((y, x): (isize, isize)) -> (isize, isize) {
-    (-x, y)
+
+pub fn part_2_with(input: &Input, side_count: SideCount) -> u64 {
+    regions_with(input, side_count)
+        .1
+        .iter()
+        .map(|region| region.area * region.sides)
+        .sum()
 }
 
-fn flood_area_sides(
-    plant: u8,
-    input: &Input,
-    visited: &mut FxHashSet<(usize, usize)>,
-    visited_edge: &mut FxHashSet<((isize, isize), (usize, usize))>,
-    (y, x): (usize, usize),
-) -> (u64, u64) {
-    if visited.contains(&(y, x)) {
-        return (0, 0);
-    }
-    visited.insert((y, x));
+pub struct Day12;
 
-    let mut area = 0;
-    let mut sides = 0;
+impl Solution for Day12 {
+    const DAY: u8 = 12;
 
-    if Some(&plant) == input.get(y).and_then(|row| row.get(x)) {
-        area += 1;
+    type Input<'a> = Input;
 
-        // explore the edges first
-        for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            if visited_edge.contains(&((dy, dx), (y, x))) {
-                continue;
-            }
-            visited_edge.insert(((dy, dx), (y, x)));
-
-            let (y2, x2) = ((y as isize + dy) as usize, (x as isize + dx) as usize);
-            match input.get(y2).and_then(|row| row.get(x2)) {
-                Some(plant2) if *plant2 == plant => {}
-                _ => {
-                    sides += 1;
-                    // now explore the whole side (without recursing)
-                    for (dy2, dx2) in [rotate_clockwise((dy, dx)), rotate_anticlockwise((dy, dx))] {
-                        let (mut y, mut x) = (y as isize + dy2, x as isize + dx2);
-                        while input.get(y as usize).and_then(|row| row.get(x as usize))
-                            == Some(&plant)
-                            && input
-                                .get((y + dy) as usize)
-                                .and_then(|row| row.get((x + dx) as usize))
-                                != Some(&plant)
-                        {
-                            visited_edge.insert(((dy, dx), (y as usize, x as usize)));
-                            (y, x) = (y + dy2, x + dx2);
-                        }
-                    }
-                }
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input_generator(input)
+    }
 
-        // then explore the interior
-        for (dy, dx) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            let (y2, x2) = ((y as isize + dy) as usize, (x as isize + dx) as usize);
-            match input.get(y2).and_then(|row| row.get(x2)) {
-                Some(plant2) if *plant2 == plant => {
-                    let (a2, p2) = flood_area_sides(plant, input, visited, visited_edge, (y2, x2));
-                    area += a2;
-                    sides += p2;
-                }
-                _ => {}
-            }
-        }
+    fn part_1(input: &Self::Input<'_>) -> impl Display {
+        part_1(input)
     }
 
-    (area, sides)
+    fn part_2(input: &Self::Input<'_>) -> impl Display {
+        part_2(input)
+    }
+
+    const VARIANTS: &'static [(&'static str, Day)] = &[("corners", Day::new::<Day12Corners>())];
 }
 
-pub fn part_2(input: &Input) -> u64 {
-    let mut visited = FxHashSet::default();
-    let mut visited_edge = FxHashSet::default();
+/// Day 12 counting the sides of each region by its corners.
+pub struct Day12Corners;
 
-    let mut sum = 0;
+impl Solution for Day12Corners {
+    const DAY: u8 = 12;
 
-    for (y, row) in input.iter().enumerate() {
-        for (x, &col) in row.iter().enumerate() {
-            let (area, sides) =
-                flood_area_sides(col, input, &mut visited, &mut visited_edge, (y, x));
+    type Input<'a> = Input;
 
-            sum += area * sides;
-        }
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input_generator(input)
     }
 
-    sum
+    fn part_1(input: &Self::Input<'_>) -> impl Display {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input<'_>) -> impl Display {
+        part_2_with(input, SideCount::Corners)
+    }
 }
 
 #[cfg(test)]
@@ -159,7 +178,7 @@ mod tests {
             BBCC
             EEEC
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 140);
         assert_eq!(part_2(&input), 80);
 
@@ -171,7 +190,7 @@ mod tests {
             OXOXO
             OOOOO
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 772);
 
         let input = input_generator(indoc! {
@@ -182,7 +201,7 @@ mod tests {
             EXXXX
             EEEEE
             "
-        });
+        }).unwrap();
         assert_eq!(part_2(&input), 236);
 
         let input = input_generator(indoc! {
@@ -194,7 +213,7 @@ mod tests {
             ABBAAA
             AAAAAA
             "
-        });
+        }).unwrap();
         assert_eq!(part_2(&input), 368);
 
         let input = input_generator(indoc! {
@@ -210,15 +229,73 @@ mod tests {
             MIIISIJEEE
             MMMISSJEEE
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 1930);
         assert_eq!(part_2(&input), 1206);
     }
 
+    #[test]
+    fn test_regions() {
+        let input = input_generator("AAAA\nBBCD\nBBCC\nEEEC\n").unwrap();
+        let (labels, stats) = regions(&input);
+        assert_eq!(labels[Pos::new(2, 3)], 2);
+        assert_eq!(
+            stats[2],
+            Region {
+                plant: b'C',
+                area: 4,
+                perimeter: 10,
+                sides: 8,
+                min: Pos::new(1, 2),
+                max: Pos::new(3, 3),
+            }
+        );
+
+        // big enough to overflow the stack if the labelling recursed
+        let size = 1000;
+        let input = input_generator(&format!("{}\n", "A".repeat(size)).repeat(size)).unwrap();
+        let (_, stats) = regions(&input);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(
+            (stats[0].area, stats[0].perimeter, stats[0].sides),
+            (size as u64 * size as u64, 4 * size as u64, 4)
+        );
+    }
+
+    #[test]
+    fn test_side_counts_agree() {
+        let mut random = crate::tests::random(0x2545f4914f6cdd1d);
+
+        for _ in 0..200 {
+            let (width, height) = (1 + random(12) as usize, 1 + random(12) as usize);
+            let plants = 1 + random(4) as u8;
+            let input = Grid::from_fn(width, height, |_| b'A' + random(plants as u64) as u8);
+
+            let edges = regions_with(&input, SideCount::Edges).1;
+            let corners = regions_with(&input, SideCount::Corners).1;
+            assert_eq!(edges, corners, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_corners_variant() {
+        let corners = crate::day(12).unwrap().variant("corners").unwrap();
+        let config = crate::runner::Config {
+            parts: [false, true],
+            warmup: 0,
+            samples: 1,
+        };
+        let report = crate::runner::run(corners, "AAAA\nBBCD\nBBCC\nEEEC\n", &config).unwrap();
+        assert_eq!(report.answers, [None, Some("80".to_string())]);
+    }
+
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day12.txt"));
+        let input = input_generator(include_str!("../../input/2024/day12.txt")).unwrap();
         assert_eq!(part_1(&input), 1485656);
-        // assert_eq!(part_2(&input),);
+        assert_eq!(
+            part_2_with(&input, SideCount::Edges),
+            part_2_with(&input, SideCount::Corners)
+        );
     }
 }
@@ -0,0 +1,278 @@
+use std::fmt::Write;
+
+use fxhash::FxHashMap;
+
+use super::{regions, Input};
+use crate::grid::{Dir, Grid, Pos};
+
+/// The geometry of a region. Corners of plots are given as `Pos`es on the lattice of plot
+/// corners, so the plot at `(r, c)` spans from corner `(r, c)` to corner `(r + 1, c + 1)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shape {
+    pub plant: u8,
+    pub cells: Vec<Pos>,
+    pub area: u64,
+    pub perimeter: u64,
+    /// Counted from the corners of the boundaries
+    pub sides: u64,
+    /// The 4-connected groups of other plots surrounded by the region. A hole that touches the
+    /// outer boundary (or another hole) at a corner is traced into the same boundary, so there can
+    /// be more holes than inner boundaries.
+    pub holes: usize,
+    /// The corners of each closed boundary, the outer one first. The outer boundary runs
+    /// clockwise and the boundaries of holes run anticlockwise, so the region is always on the
+    /// right.
+    pub boundaries: Vec<Vec<Pos>>,
+}
+
+pub fn shapes(input: &Input) -> Vec<Shape> {
+    let (labels, regions) = regions(input);
+
+    let mut cells = vec![vec![]; regions.len()];
+    for (pos, &label) in labels.iter() {
+        cells[label as usize].push(pos);
+    }
+
+    regions
+        .into_iter()
+        .zip(cells)
+        .enumerate()
+        .map(|(label, (region, cells))| {
+            let boundaries = boundaries(&labels, label as u32, &cells);
+            Shape {
+                plant: region.plant,
+                area: region.area,
+                perimeter: region.perimeter,
+                sides: boundaries.iter().map(|b| b.len() as u64).sum(),
+                holes: holes(&labels, label as u32, region.min, region.max),
+                boundaries,
+                cells,
+            }
+        })
+        .collect()
+}
+
+/// Counts the groups of other plots that region `label` surrounds. A group that's surrounded
+/// can't reach past the region's bounding box (`min` to `max`), so only that needs searching.
+fn holes(labels: &Grid<u32>, label: u32, min: Pos, max: Pos) -> usize {
+    let (width, height) = (max.col - min.col + 1, max.row - min.row + 1);
+    let inside = Grid::from_fn(width, height, |p| {
+        labels[Pos::new(min.row + p.row, min.col + p.col)] == label
+    });
+    let (groups, count) = inside.label_regions(|a, b| a == b);
+
+    let mut enclosed = vec![true; count as usize];
+    for (pos, &group) in groups.iter() {
+        let border = pos.row == 0 || pos.col == 0 || pos.row + 1 == height || pos.col + 1 == width;
+        if inside[pos] || border {
+            enclosed[group as usize] = false;
+        }
+    }
+    enclosed.into_iter().filter(|&e| e).count()
+}
+
+/// Traces the fences around `cells` (all labelled `label`) into closed loops of corners.
+fn boundaries(labels: &Grid<u32>, label: u32, cells: &[Pos]) -> Vec<Vec<Pos>> {
+    // each fence as a directed edge `(start corner, heading)` with the region on its right
+    let mut edges = vec![];
+    for &pos in cells {
+        for dir in Dir::ALL {
+            if labels.offset(pos, dir).is_some_and(|p| labels[p] == label) {
+                continue;
+            }
+            let start = match dir {
+                Dir::Up => pos,
+                Dir::Right => Pos::new(pos.row, pos.col + 1),
+                Dir::Down => Pos::new(pos.row + 1, pos.col + 1),
+                Dir::Left => Pos::new(pos.row + 1, pos.col),
+            };
+            edges.push((start, dir.turn_right()));
+        }
+    }
+
+    let mut outgoing: FxHashMap<Pos, Vec<Dir>> = FxHashMap::default();
+    for &(start, heading) in &edges {
+        outgoing.entry(start).or_default().push(heading);
+    }
+
+    let mut loops = vec![];
+    for &(start, start_heading) in &edges {
+        if !outgoing[&start].contains(&start_heading) {
+            continue;
+        }
+
+        let mut corners = vec![];
+        let (mut at, mut heading) = (start, start_heading);
+        loop {
+            at = at.checked_add(heading).unwrap();
+            // Where two plots of the region only touch diagonally, turn as tightly as possible
+            // so each keeps its own corner, matching the way sides are counted.
+            let out = outgoing.get_mut(&at).expect("fences form closed loops");
+            let next = [heading.turn_right(), heading, heading.turn_left()]
+                .into_iter()
+                .find(|h| out.contains(h))
+                .expect("fences form closed loops");
+            out.retain(|&h| h != next);
+
+            if next != heading {
+                corners.push(at);
+            }
+            heading = next;
+            if (at, heading) == (start, start_heading) {
+                break;
+            }
+        }
+        // if the trace began at a corner, start from it
+        if corners.last() == Some(&start) {
+            corners.rotate_right(1);
+        }
+        loops.push(corners);
+    }
+    loops
+}
+
+const CELL_SIZE: usize = 16;
+
+/// `plant` as text that's safe anywhere in an SVG document.
+fn escape(plant: u8) -> String {
+    match plant {
+        b'&' => "&amp;".to_string(),
+        b'<' => "&lt;".to_string(),
+        b'>' => "&gt;".to_string(),
+        b'"' => "&quot;".to_string(),
+        b'\'' => "&apos;".to_string(),
+        // XML can't contain most control characters, even escaped
+        _ if plant.is_ascii_control() => format!("0x{plant:02x}"),
+        _ => (plant as char).to_string(),
+    }
+}
+
+/// Draws every region, with its corners marked and its measurements as a tooltip.
+pub fn render_svg(input: &Input, shapes: &[Shape]) -> String {
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#,
+        input.width() * CELL_SIZE,
+        input.height() * CELL_SIZE
+    )
+    .unwrap();
+
+    let point = |p: &Pos| format!("{} {}", p.col * CELL_SIZE, p.row * CELL_SIZE);
+    for shape in shapes {
+        let hue = shape.plant as usize * 47 % 360;
+        let path: String = shape
+            .boundaries
+            .iter()
+            .map(|corners| {
+                let points: Vec<_> = corners.iter().map(point).collect();
+                format!("M {} Z ", points.join(" L "))
+            })
+            .collect();
+
+        writeln!(
+            svg,
+            r#"<path d="{}" fill="hsl({hue}, 60%, 70%)" fill-rule="evenodd" stroke="black">"#,
+            path.trim_end()
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<title>{}: area {}, perimeter {}, sides {}, holes {}</title></path>",
+            escape(shape.plant),
+            shape.area,
+            shape.perimeter,
+            shape.sides,
+            shape.holes
+        )
+        .unwrap();
+        for corner in shape.boundaries.iter().flatten() {
+            writeln!(
+                svg,
+                r#"<circle cx="{}" cy="{}" r="2"/>"#,
+                corner.col * CELL_SIZE,
+                corner.row * CELL_SIZE
+            )
+            .unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_12::input_generator;
+
+    #[test]
+    fn test_shapes() {
+        let input = input_generator("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO\n").unwrap();
+        let shapes = shapes(&input);
+        assert_eq!(shapes.len(), 5);
+
+        let outer = &shapes[0];
+        assert_eq!((outer.plant, outer.area, outer.holes), (b'O', 21, 4));
+        assert_eq!(outer.sides, 20);
+        assert_eq!(
+            outer.boundaries[0],
+            [
+                Pos::new(0, 0),
+                Pos::new(0, 5),
+                Pos::new(5, 5),
+                Pos::new(5, 0)
+            ]
+        );
+        // holes run anticlockwise
+        assert_eq!(
+            outer.boundaries[1],
+            [
+                Pos::new(1, 2),
+                Pos::new(1, 1),
+                Pos::new(2, 1),
+                Pos::new(2, 2)
+            ]
+        );
+
+        let hole = &shapes[1];
+        assert_eq!(hole.cells, [Pos::new(1, 1)]);
+        assert_eq!((hole.perimeter, hole.sides, hole.holes), (4, 4, 0));
+
+        let svg = render_svg(&input, &shapes);
+        assert_eq!(svg.matches("<path").count(), 5);
+        assert!(svg.contains("O: area 21, perimeter 36, sides 20, holes 4"));
+    }
+
+    #[test]
+    fn test_sides_match_regions() {
+        let input = input_generator("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n").unwrap();
+        let (_, regions) = regions(&input);
+        let shapes = shapes(&input);
+        // the two B regions touch diagonally, so their fences trace as one loop with 8 corners
+        assert_eq!(shapes[0].holes, 2);
+        assert_eq!(shapes[0].boundaries.len(), 2);
+        assert_eq!(shapes[0].boundaries[1].len(), 8);
+        for (region, shape) in regions.iter().zip(&shapes) {
+            assert_eq!(region.sides, shape.sides);
+        }
+    }
+
+    #[test]
+    fn test_hole_touching_outside() {
+        // the middle plot is fenced in on all four sides, but touches the outside at a corner
+        let input = input_generator("AA.\nA.A\nAAA\n").unwrap();
+        let shapes = shapes(&input);
+        assert_eq!((shapes[0].plant, shapes[0].holes), (b'A', 1));
+        assert_eq!(shapes[0].boundaries.len(), 1);
+        assert_eq!(shapes[1].holes, 0);
+    }
+
+    #[test]
+    fn test_svg_escapes_plants() {
+        let input = input_generator("&<\n").unwrap();
+        let svg = render_svg(&input, &shapes(&input));
+        assert!(svg.contains("<title>&amp;: area 1"));
+        assert!(svg.contains("<title>&lt;: area 1"));
+    }
+}
@@ -1,5 +1,7 @@
 use winnow::{ascii::*, combinator::*, Parser};
 
+use crate::parse::ParseError;
+
 pub type Input = Vec<Machine>;
 
 pub struct Machine {
@@ -34,8 +36,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     .parse_next(input)
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input)?)
 }
 
 const A_COST: i64 = 3;
@@ -91,6 +93,8 @@ pub fn part_2(input: &Input) -> u64 {
         .sum()
 }
 
+solution!(Day13, 13, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,14 +120,14 @@ mod tests {
             Button B: X+27, Y+71
             Prize: X=18641, Y=10279
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), 480);
         assert_eq!(part_2(&input), 875318608908);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/day13.txt"));
+        let input = input_generator(include_str!("../../input/2024/day13.txt")).unwrap();
         assert_eq!(part_1(&input), 31065);
         assert_eq!(part_2(&input), 93866170395343);
     }
@@ -5,6 +5,8 @@ use winnow::{
     Parser,
 };
 
+use crate::parse::ParseError;
+
 pub struct Input {
 
 }
@@ -13,8 +15,8 @@ fn parse_input(input: &mut &str) -> winnow::PResult<Input> {
     todo!()
 }
 
-pub fn input_generator(input: &str) -> Input {
-    parse_input.parse(input.trim_end()).unwrap()
+pub fn input_generator(input: &str) -> Result<Input, ParseError> {
+    Ok(parse_input.parse(input.trim_end())?)
 }
 
 pub fn part_1(input: &Input) -> u32 {
@@ -25,6 +27,8 @@ pub fn part_2(input: &Input) -> u32 {
     0
 }
 
+solution!(Dayxx, xx, Input);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -35,14 +39,14 @@ mod tests {
         let input = input_generator(indoc! {
             "
             "
-        });
+        }).unwrap();
         assert_eq!(part_1(&input), );
         // assert_eq!(part_2(&input),);
     }
 
     #[test]
     fn test_my_input() {
-        let input = input_generator(include_str!("../../input/2024/dayxx.txt"));
+        let input = input_generator(include_str!("../../input/2024/dayxx.txt")).unwrap();
         // assert_eq!(part_1(&input), );
         // assert_eq!(part_2(&input),);
     }
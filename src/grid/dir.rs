@@ -0,0 +1,167 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A `(row, col)` step between positions, with rows increasing downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec2 {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Vec2 {
+    pub const UP: Vec2 = Vec2::new(-1, 0);
+    pub const RIGHT: Vec2 = Vec2::new(0, 1);
+    pub const DOWN: Vec2 = Vec2::new(1, 0);
+    pub const LEFT: Vec2 = Vec2::new(0, -1);
+
+    /// Up, right, down, left.
+    pub const CARDINAL: [Vec2; 4] = [Vec2::UP, Vec2::RIGHT, Vec2::DOWN, Vec2::LEFT];
+    /// Clockwise from up-left.
+    pub const DIAGONAL: [Vec2; 4] = [
+        Vec2::new(-1, -1),
+        Vec2::new(-1, 1),
+        Vec2::new(1, 1),
+        Vec2::new(1, -1),
+    ];
+    /// Clockwise from up.
+    pub const ALL: [Vec2; 8] = [
+        Vec2::new(-1, 0),
+        Vec2::new(-1, 1),
+        Vec2::new(0, 1),
+        Vec2::new(1, 1),
+        Vec2::new(1, 0),
+        Vec2::new(1, -1),
+        Vec2::new(0, -1),
+        Vec2::new(-1, -1),
+    ];
+
+    pub const fn new(row: isize, col: isize) -> Self {
+        Vec2 { row, col }
+    }
+
+    pub fn rotate_clockwise(self) -> Self {
+        Vec2::new(self.col, -self.row)
+    }
+
+    pub fn rotate_anticlockwise(self) -> Self {
+        Vec2::new(-self.col, self.row)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.row + rhs.row, self.col + rhs.col)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.row - rhs.row, self.col - rhs.col)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: isize) -> Vec2 {
+        Vec2::new(self.row * rhs, self.col * rhs)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// Clockwise from up.
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    pub fn turn_right(self) -> Self {
+        Dir::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Dir::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Parses the arrows `^>v<`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Dir::Up),
+            '>' => Some(Dir::Right),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Dir::Up => '^',
+            Dir::Right => '>',
+            Dir::Down => 'v',
+            Dir::Left => '<',
+        }
+    }
+}
+
+impl From<Dir> for Vec2 {
+    fn from(dir: Dir) -> Vec2 {
+        Vec2::CARDINAL[dir as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Pos;
+
+    #[test]
+    fn test_turning() {
+        for dir in Dir::ALL {
+            let v = Vec2::from(dir);
+            assert_eq!(Vec2::from(dir.turn_right()), v.rotate_clockwise());
+            assert_eq!(Vec2::from(dir.turn_left()), v.rotate_anticlockwise());
+            assert_eq!(Vec2::from(dir.reverse()), -v);
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(Dir::from_char(dir.to_char()), Some(dir));
+        }
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(
+            Vec2::DIAGONAL.map(|d| d.rotate_clockwise())[0],
+            Vec2::DIAGONAL[1]
+        );
+    }
+
+    #[test]
+    fn test_vec2() {
+        let pos = Pos::new(2, 3);
+        assert_eq!(pos.checked_add(Dir::Left), Some(Pos::new(2, 2)));
+        assert_eq!(pos.checked_add(Vec2::LEFT * 4), None);
+        assert_eq!(pos.delta_to(Pos::new(0, 4)), Vec2::new(-2, 1));
+        assert_eq!(
+            Vec2::new(1, 2) + Vec2::new(3, 4) - Vec2::DOWN,
+            Vec2::new(3, 6)
+        );
+    }
+}
@@ -0,0 +1,287 @@
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{grid_lines, ParseError};
+
+mod dir;
+
+pub use dir::{Dir, Vec2};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+
+    /// `self` moved by `delta`, if that doesn't go below zero on either axis.
+    pub fn checked_add(self, delta: impl Into<Vec2>) -> Option<Pos> {
+        let delta = delta.into();
+        Some(Pos::new(
+            self.row.checked_add_signed(delta.row)?,
+            self.col.checked_add_signed(delta.col)?,
+        ))
+    }
+
+    /// The step that takes `self` to `other`.
+    pub fn delta_to(self, other: Pos) -> Vec2 {
+        Vec2::new(
+            other.row as isize - self.row as isize,
+            other.col as isize - self.col as isize,
+        )
+    }
+}
+
+/// A rectangular grid stored row-major in a single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|row| (0..width).map(move |col| Pos::new(row, col)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /// Parses one cell per byte, with `f` converting each byte (or explaining why it can't).
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(Pos, u8) -> Result<T, String>,
+    ) -> Result<Self, ParseError> {
+        let lines = grid_lines(input)?;
+        let width = lines.first().map_or(0, |line| line.len());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            for (col, &b) in line.iter().enumerate() {
+                let cell = f(Pos::new(row, col), b).map_err(|message| {
+                    let offset = line.as_ptr() as usize - input.as_ptr() as usize + col;
+                    ParseError::at(input, offset, message)
+                })?;
+                cells.push(cell);
+            }
+        }
+
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.row * self.width + pos.col])
+    }
+
+    /// `pos` moved by `delta`, if that's still in the grid.
+    pub fn offset(&self, pos: Pos, delta: impl Into<Vec2>) -> Option<Pos> {
+        pos.checked_add(delta).filter(|&pos| self.contains(pos))
+    }
+
+    /// `pos` moved by `delta`, wrapping around the edges as if the grid were a torus.
+    pub fn wrapping_offset(&self, pos: Pos, delta: impl Into<Vec2>) -> Pos {
+        let delta = delta.into();
+        let wrap =
+            |x: usize, d: isize, len: usize| (x as isize + d).rem_euclid(len as isize) as usize;
+        Pos::new(
+            wrap(pos.row, delta.row, self.height),
+            wrap(pos.col, delta.col, self.width),
+        )
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Vec2::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Vec2::ALL
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// The positions reached by repeatedly stepping `delta` from `pos` (not including `pos`
+    /// itself), up to the edge of the grid. Stepping diagonally walks a diagonal.
+    pub fn ray(&self, pos: Pos, delta: impl Into<Vec2>) -> impl Iterator<Item = Pos> + '_ {
+        let delta = delta.into();
+        std::iter::successors(self.offset(pos, delta), move |&p| self.offset(p, delta))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Pos::new(row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..][..self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is out of bounds");
+        // `step_by` panics on a zero width, like `chunks` in `rows`
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    /// Labels the 4-connected regions in which neighbouring cells are `same`, numbering them
+    /// from 0 in the order their first cell appears. Returns the label grid and the number of
+    /// regions.
+    pub fn label_regions(&self, mut same: impl FnMut(&T, &T) -> bool) -> (Grid<u32>, u32) {
+        const UNLABELLED: u32 = u32::MAX;
+        let mut labels = self.map(|_| UNLABELLED);
+        let mut count = 0;
+        let mut stack = vec![];
+        for pos in self.positions() {
+            if labels[pos] != UNLABELLED {
+                continue;
+            }
+
+            // an explicit stack, recursing would overflow on large regions
+            labels[pos] = count;
+            stack.push(pos);
+            while let Some(p) = stack.pop() {
+                for q in self.neighbours4(p) {
+                    if labels[q] == UNLABELLED && same(&self[p], &self[q]) {
+                        labels[q] = count;
+                        stack.push(q);
+                    }
+                }
+            }
+            count += 1;
+        }
+        (labels, count)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Self::parse_with(input, |_, b| Ok(b))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{pos:?} is out of bounds");
+        &self.cells[pos.row * self.width + pos.col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{pos:?} is out of bounds");
+        &mut self.cells[pos.row * self.width + pos.col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::parse("abc\ndef\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(1, 2)], b'f');
+        assert_eq!(grid.get(Pos::new(2, 0)), None);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [b"abc", b"def"]);
+        assert_eq!(grid.row(1), b"def");
+        assert!(grid.column(1).eq(b"be"));
+        assert!(grid.column(2).eq(b"cf"));
+        assert!(std::panic::catch_unwind(|| grid.column(3).count()).is_err());
+        assert_eq!(Grid::new(2, 0, 0u8).column(1).count(), 0);
+        assert!(grid
+            .ray(Pos::new(0, 0), Vec2::new(1, 1))
+            .map(|p| grid[p])
+            .eq(*b"e"));
+        assert_eq!(grid.offset(Pos::new(0, 0), Dir::Up), None);
+        assert_eq!(
+            grid.wrapping_offset(Pos::new(0, 0), Dir::Up),
+            Pos::new(1, 0)
+        );
+        assert_eq!(
+            grid.wrapping_offset(Pos::new(1, 2), Vec2::new(1, 1)),
+            Pos::new(0, 0)
+        );
+
+        assert_eq!(
+            grid.neighbours4(Pos::new(0, 0)).collect::<Vec<_>>(),
+            [Pos::new(0, 1), Pos::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours8(Pos::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 5);
+
+        let (labels, count) = Grid::parse("aab\nbab\n")
+            .unwrap()
+            .label_regions(|a, b| a == b);
+        assert_eq!(count, 3);
+        assert_eq!(labels.rows().collect::<Vec<_>>(), [[0, 0, 1], [2, 0, 1]]);
+
+        let e = Grid::parse("ab\nc\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        let e = Grid::parse_with("..\n.#\n", |_, b| match b {
+            b'.' => Ok(false),
+            _ => Err("bad".to_string()),
+        })
+        .unwrap_err();
+        assert_eq!((e.line, e.column), (2, 2));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = "input/2024";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "-" => Source::Stdin,
+            path => Source::File(path.into()),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Source::File(path) => std::fs::read_to_string(path),
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The directory holding `day{N}.txt` inputs, taken from (in order of preference) the explicit
+/// `dir`, the value of [`INPUT_DIR_ENV`] or [`DEFAULT_INPUT_DIR`].
+pub fn input_dir(dir: Option<&Path>) -> PathBuf {
+    resolve_input_dir(dir, std::env::var_os(INPUT_DIR_ENV).map(PathBuf::from))
+}
+
+fn resolve_input_dir(dir: Option<&Path>, env: Option<PathBuf>) -> PathBuf {
+    dir.map(Path::to_path_buf)
+        .or(env.filter(|env| !env.as_os_str().is_empty()))
+        .unwrap_or_else(|| DEFAULT_INPUT_DIR.into())
+}
+
+pub fn day_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}.txt"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_input_dir() {
+        let flag = Path::new("flag");
+        assert_eq!(
+            resolve_input_dir(Some(flag), Some("env".into())),
+            Path::new("flag")
+        );
+        assert_eq!(
+            resolve_input_dir(None, Some("env".into())),
+            Path::new("env")
+        );
+        assert_eq!(
+            resolve_input_dir(None, Some("".into())),
+            Path::new(DEFAULT_INPUT_DIR)
+        );
+        assert_eq!(resolve_input_dir(None, None), Path::new(DEFAULT_INPUT_DIR));
+        assert_eq!(
+            day_path(Path::new("dir"), 6),
+            Path::new("dir").join("day6.txt")
+        );
+    }
+}
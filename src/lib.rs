@@ -1,13 +1,248 @@
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
+use std::{fmt::Display, io, str::FromStr};
+
+use parse::ParseError;
+
+pub mod client;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+
+pub trait Solution {
+    const DAY: u8;
+
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part_1(input: &Self::Input<'_>) -> impl Display;
+    fn part_2(input: &Self::Input<'_>) -> impl Display;
+
+    /// Alternative implementations of the day that the runner can pick by name.
+    const VARIANTS: &'static [(&'static str, Day)] = &[];
+
+    /// Draws how `part` is solved, or returns `None` if this day has no visualisation.
+    fn visualize(
+        _input: &Self::Input<'_>,
+        _part: u8,
+        _style: Visualization,
+        _out: &mut dyn io::Write,
+    ) -> Option<io::Result<()>> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Visualization {
+    /// Redraw each frame in place in the terminal, with colour
+    Animate,
+    /// Plain text frames one after the other
+    Frames,
+}
+
+impl FromStr for Visualization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "animate" => Ok(Visualization::Animate),
+            "frames" => Ok(Visualization::Frames),
+            _ => Err(format!(
+                "invalid visualisation '{s}', expected animate or frames"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum VisualizeError {
+    Unsupported,
+    Parse(ParseError),
+    Io(io::Error),
+}
+
+impl Display for VisualizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VisualizeError::Unsupported => write!(f, "there's no visualisation for this day"),
+            VisualizeError::Parse(e) => write!(f, "failed to parse input: {e}"),
+            VisualizeError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for VisualizeError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Step {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Step {
+    pub const ALL: [Step; 3] = [Step::Parse, Step::Part1, Step::Part2];
+
+    /// The part this step solves, `None` for parsing.
+    pub fn part(self) -> Option<u8> {
+        match self {
+            Step::Parse => None,
+            Step::Part1 => Some(1),
+            Step::Part2 => Some(2),
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Step::Parse => "parse",
+            Step::Part1 => "1",
+            Step::Part2 => "2",
+        })
+    }
+}
+
+/// Drives the steps of a [`Day`]. Implementations decide whether (and how many times) each step
+/// runs, which is where timing, benchmarking and part selection live.
+pub trait Harness {
+    fn step(&mut self, step: Step, f: &mut dyn FnMut());
+}
+
+pub type Answers = [Option<String>; 2];
+
+/// A type-erased [`Solution`], so that days with different input and answer types can be
+/// iterated over.
+pub struct Day {
+    pub day: u8,
+    variants: &'static [(&'static str, Day)],
+    run: fn(&str, &mut dyn Harness) -> Result<Answers, ParseError>,
+    visualize: fn(&str, u8, Visualization, &mut dyn io::Write) -> Result<(), VisualizeError>,
+}
+
+fn run<S: Solution>(input: &str, harness: &mut dyn Harness) -> Result<Answers, ParseError> {
+    let mut parsed = None;
+    harness.step(Step::Parse, &mut || parsed = Some(S::parse(input)));
+    let Some(parsed) = parsed.transpose()? else {
+        return Ok([None, None]);
+    };
+
+    let mut part_1 = None;
+    harness.step(Step::Part1, &mut || {
+        part_1 = Some(S::part_1(&parsed).to_string())
+    });
+    let mut part_2 = None;
+    harness.step(Step::Part2, &mut || {
+        part_2 = Some(S::part_2(&parsed).to_string())
+    });
+
+    Ok([part_1, part_2])
+}
+
+fn visualize<S: Solution>(
+    input: &str,
+    part: u8,
+    style: Visualization,
+    out: &mut dyn io::Write,
+) -> Result<(), VisualizeError> {
+    let parsed = S::parse(input).map_err(VisualizeError::Parse)?;
+    match S::visualize(&parsed, part, style, out) {
+        Some(result) => result.map_err(VisualizeError::Io),
+        None => Err(VisualizeError::Unsupported),
+    }
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            variants: S::VARIANTS,
+            run: run::<S>,
+            visualize: visualize::<S>,
+        }
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&'static Day> {
+        self.variants
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, day)| day)
+    }
+
+    pub fn variant_names(&self) -> impl Iterator<Item = &'static str> {
+        self.variants.iter().map(|(name, _)| *name)
+    }
+
+    pub fn run(&self, input: &str, harness: &mut dyn Harness) -> Result<Answers, ParseError> {
+        (self.run)(input, harness)
+    }
+
+    pub fn visualize(
+        &self,
+        input: &str,
+        part: u8,
+        style: Visualization,
+        out: &mut dyn io::Write,
+    ) -> Result<(), VisualizeError> {
+        (self.visualize)(input, part, style, out)
+    }
+}
+
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+/// Declares `$name` as the [`Solution`] for day `$day`, forwarding to the calling module's
+/// `input_generator`, `part_1` and `part_2`.
+macro_rules! solution {
+    ($name:ident, $day:literal, $input:ty) => {
+        pub struct $name;
+
+        impl $crate::Solution for $name {
+            const DAY: u8 = $day;
+
+            type Input<'a> = $input;
+
+            fn parse(input: &str) -> Result<Self::Input<'_>, $crate::parse::ParseError> {
+                input_generator(input)
+            }
+
+            fn part_1(input: &Self::Input<'_>) -> impl std::fmt::Display {
+                part_1(input)
+            }
+
+            fn part_2(input: &Self::Input<'_>) -> impl std::fmt::Display {
+                part_2(input)
+            }
+        }
+    };
+}
+
+// `pub mod day_NN;` for each day, and the `DAYS` registry (see build.rs)
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A xorshift generator of numbers below `n`, for tests that want random inputs that are the
+    /// same on every run.
+    pub(crate) fn random(seed: u64) -> impl FnMut(u64) -> u64 {
+        let mut state = seed;
+        move |n| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state % n
+        }
+    }
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+        for day in DAYS {
+            assert!(day.variants.iter().all(|(_, v)| v.day == day.day));
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+
+use winnow::error::ContextError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the offending input
+    pub line: usize,
+    /// 1-based column (in chars) of the offending input
+    pub column: usize,
+    /// The whole line containing the offending input
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        ParseError {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Reports an error at `substr`, which must be a subslice of `input`.
+    pub fn at_substr(input: &str, substr: &str, message: impl Into<String>) -> Self {
+        let offset = (substr.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        Self::at(input, offset, message)
+    }
+}
+
+impl From<winnow::error::ParseError<&str, ContextError>> for ParseError {
+    fn from(e: winnow::error::ParseError<&str, ContextError>) -> Self {
+        let message = match e.inner().to_string() {
+            m if m.is_empty() => "unexpected input".to_string(),
+            m => m,
+        };
+        ParseError::at(e.input(), e.offset(), message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = self.line.to_string().len();
+        writeln!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{:gutter$} | {:>col$}", "", "^", col = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits a grid into rows, checking that every row has the same width.
+pub fn grid_lines(input: &str) -> Result<Vec<&[u8]>, ParseError> {
+    let lines: Vec<_> = input.lines().collect();
+    let width = lines.first().map_or(0, |line| line.len());
+    match lines.iter().find(|line| line.len() != width) {
+        Some(line) => Err(ParseError::at_substr(
+            input,
+            line,
+            format!(
+                "expected a row of width {width}, found width {}",
+                line.len()
+            ),
+        )),
+        None => Ok(lines.into_iter().map(str::as_bytes).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winnow::{ascii::dec_uint, Parser};
+
+    #[test]
+    fn test_position() {
+        let input = "123\n45x\n6";
+        let e: ParseError = dec_uint::<_, u32, ContextError>
+            .parse(input)
+            .unwrap_err()
+            .into();
+        assert_eq!((e.line, e.column, e.snippet.as_str()), (1, 4, "123"));
+
+        let e = ParseError::at_substr(input, &input[6..], "bad");
+        assert_eq!((e.line, e.column, e.snippet.as_str()), (2, 3, "45x"));
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 3: bad\n  |\n2 | 45x\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_grid_lines() {
+        assert_eq!(grid_lines("ab\ncd\n").unwrap(), [b"ab", b"cd"]);
+        let e = grid_lines("abc\ndef\ng").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::Path,
+    time::Duration,
+};
+
+use super::Report;
+use crate::{parse::ParseError, Step};
+
+pub const DEFAULT_BASELINE_PATH: &str = "baseline.tsv";
+
+const HEADER: &str = "day\tstep\tmedian_ns";
+
+/// Median timings of each step from a previous run, keyed by `(day, step)`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    timings: BTreeMap<(u8, Step), Duration>,
+}
+
+pub struct Comparison {
+    pub day: u8,
+    pub step: Step,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    /// The relative change from the baseline, e.g. `0.1` for 10% slower.
+    pub fn change(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+impl Baseline {
+    pub fn from_reports(reports: &[Report]) -> Self {
+        let timings = reports
+            .iter()
+            .flat_map(|report| {
+                Step::ALL
+                    .into_iter()
+                    .zip(&report.timings)
+                    .filter_map(|(step, stats)| Some(((report.day, step), stats.as_ref()?.median)))
+            })
+            .collect();
+        Baseline { timings }
+    }
+
+    /// Overwrites timings in `self` with those in `other`, keeping any that `other` doesn't have.
+    pub fn update(&mut self, other: Baseline) {
+        self.timings.extend(other.timings);
+    }
+
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut timings = BTreeMap::new();
+        for line in input.lines().skip_while(|line| *line == HEADER) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let err = || ParseError::at_substr(input, line, "expected DAY\tSTEP\tMEDIAN_NS");
+            let mut fields = line.split('\t');
+            let day = fields.next().and_then(|f| f.parse().ok()).ok_or_else(err)?;
+            let step = match fields.next() {
+                Some("parse") => Step::Parse,
+                Some("1") => Step::Part1,
+                Some("2") => Step::Part2,
+                _ => return Err(err()),
+            };
+            let median = fields.next().and_then(|f| f.parse().ok()).ok_or_else(err)?;
+            timings.insert((day, step), Duration::from_nanos(median));
+        }
+        Ok(Baseline { timings })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&input).map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = String::from(HEADER);
+        for ((day, step), median) in &self.timings {
+            out += &format!("\n{day}\t{step}\t{}", median.as_nanos());
+        }
+        out.push('\n');
+        std::fs::write(path, out)
+    }
+
+    /// Compares each step in `reports` that has a baseline, flagging those that are more than
+    /// `threshold` (e.g. `0.1` for 10%) slower.
+    pub fn compare(&self, reports: &[Report], threshold: f64) -> Vec<Comparison> {
+        Baseline::from_reports(reports)
+            .timings
+            .into_iter()
+            .filter_map(|((day, step), current)| {
+                let baseline = *self.timings.get(&(day, step))?;
+                let mut comparison = Comparison {
+                    day,
+                    step,
+                    baseline,
+                    current,
+                    regressed: false,
+                };
+                comparison.regressed = comparison.change() > threshold;
+                Some(comparison)
+            })
+            .collect()
+    }
+}
+
+pub fn write_table(mut w: impl Write, comparisons: &[Comparison]) -> io::Result<()> {
+    writeln!(
+        w,
+        "{:>3} {:<5} {:>10} {:>10} {:>8}",
+        "day", "step", "baseline", "current", "change"
+    )?;
+    for c in comparisons {
+        writeln!(
+            w,
+            "{:>3} {:<5} {:>10} {:>10} {:>+7.1}%{}",
+            c.day,
+            c.step.to_string(),
+            format!("{:.1?}", c.baseline),
+            format!("{:.1?}", c.current),
+            c.change() * 100.0,
+            if c.regressed { "  SLOWER" } else { "" }
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Stats;
+
+    fn report(day: u8, parse_us: u64, part_1_us: u64) -> Report {
+        let stats = |us| Stats::from_samples(&mut [Duration::from_micros(us)]);
+        Report {
+            day,
+            answers: [Some("0".to_string()), None],
+            timings: [stats(parse_us), stats(part_1_us), None],
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_compare() {
+        let mut baseline = Baseline::from_reports(&[report(1, 100, 100), report(2, 10, 10)]);
+        baseline.update(Baseline::from_reports(&[report(2, 20, 20)]));
+
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}", std::process::id()));
+        baseline.save(&path).unwrap();
+        let loaded = Baseline::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, baseline);
+        assert_eq!(loaded.timings.len(), 4);
+
+        let comparisons = loaded.compare(&[report(2, 21, 30), report(3, 1, 1)], 0.1);
+        let flagged: Vec<_> = comparisons
+            .iter()
+            .map(|c| (c.day, c.step, c.regressed))
+            .collect();
+        assert_eq!(flagged, [(2, Step::Parse, false), (2, Step::Part1, true)]);
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::Report;
+use crate::Step;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+
+        let n = samples.len();
+        let median = match n % 2 {
+            0 => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+            _ => samples[n / 2],
+        };
+        let mean = samples.iter().map(Duration::as_secs_f64).sum::<f64>() / n as f64;
+        let variance = match n {
+            1 => 0.0,
+            _ => {
+                samples
+                    .iter()
+                    .map(|s| (s.as_secs_f64() - mean).powi(2))
+                    .sum::<f64>()
+                    / (n - 1) as f64
+            }
+        };
+
+        Some(Stats {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+const HEADER: &str = "timestamp\tday\tstep\tsamples\tmin_ns\tmedian_ns\tmean_ns\tstddev_ns";
+
+/// Appends the timings in `reports` to the tab-separated file at `path` (creating it if needed),
+/// so that results can be compared between runs.
+pub fn append_results(path: &Path, reports: &[Report]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{HEADER}")?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+
+    for report in reports {
+        for (step, stats) in Step::ALL.iter().zip(&report.timings) {
+            if let Some(s) = stats {
+                writeln!(
+                    file,
+                    "{timestamp}\t{}\t{step}\t{}\t{}\t{}\t{}\t{}",
+                    report.day,
+                    s.samples,
+                    s.min.as_nanos(),
+                    s.median.as_nanos(),
+                    s.mean.as_nanos(),
+                    s.stddev.as_nanos(),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut samples = [5, 1, 3, 2, 4].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean.as_micros(), 3000);
+        // sqrt(10 / 4)
+        assert_eq!(stats.stddev.as_micros(), 1581);
+
+        let mut samples = [Duration::from_millis(4), Duration::from_millis(2)];
+        assert_eq!(
+            Stats::from_samples(&mut samples).unwrap().median,
+            Duration::from_millis(3)
+        );
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+}
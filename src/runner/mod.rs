@@ -0,0 +1,81 @@
+use std::time::{Duration, Instant};
+
+use crate::{parse::ParseError, Answers, Day, Harness, Step};
+
+pub mod baseline;
+pub mod bench;
+pub mod cpu;
+pub mod output;
+pub mod verify;
+
+pub use bench::Stats;
+
+pub struct Config {
+    /// Which of part 1 and part 2 to run
+    pub parts: [bool; 2],
+    /// Untimed runs of each step before sampling starts
+    pub warmup: usize,
+    /// Timed runs of each step
+    pub samples: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            parts: [true; 2],
+            warmup: 0,
+            samples: 1,
+        }
+    }
+}
+
+pub struct Report {
+    pub day: u8,
+    pub answers: Answers,
+    /// Indexed by [`Step`]
+    pub timings: [Option<Stats>; 3],
+}
+
+struct Sampler<'a> {
+    config: &'a Config,
+    samples: [Vec<Duration>; 3],
+}
+
+impl Harness for Sampler<'_> {
+    fn step(&mut self, step: Step, f: &mut dyn FnMut()) {
+        let run = match step {
+            Step::Parse => true,
+            Step::Part1 => self.config.parts[0],
+            Step::Part2 => self.config.parts[1],
+        };
+        if !run {
+            return;
+        }
+
+        for _ in 0..self.config.warmup {
+            f();
+        }
+        let samples = &mut self.samples[step as usize];
+        for _ in 0..self.config.samples.max(1) {
+            let instant = Instant::now();
+            f();
+            samples.push(instant.elapsed());
+        }
+    }
+}
+
+pub fn run(day: &Day, input: &str, config: &Config) -> Result<Report, ParseError> {
+    let mut sampler = Sampler {
+        config,
+        samples: Default::default(),
+    };
+    let answers = day.run(input, &mut sampler)?;
+
+    Ok(Report {
+        day: day.day,
+        answers,
+        timings: sampler
+            .samples
+            .map(|mut samples| Stats::from_samples(&mut samples)),
+    })
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use super::{
+    verify::{Expected, Verdict},
+    Report,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("invalid format '{s}', expected text, json or csv")),
+        }
+    }
+}
+
+/// A single answered part, flattened out of a [`Report`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub answer: &'a str,
+    pub parse_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+}
+
+pub fn entries(reports: &[Report]) -> impl Iterator<Item = Entry<'_>> {
+    reports.iter().flat_map(|report| {
+        let parse_ns = report.timings[0].map(|s| s.median.as_nanos());
+        (0..2).filter_map(move |part| {
+            Some(Entry {
+                day: report.day,
+                part: part as u8 + 1,
+                answer: report.answers[part].as_deref()?,
+                parse_ns,
+                solve_ns: report.timings[part + 1].map(|s| s.median.as_nanos()),
+            })
+        })
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+fn opt<T: ToString>(v: Option<T>, none: &str) -> String {
+    v.map_or(none.to_string(), |v| v.to_string())
+}
+
+fn status(expected: &Expected, e: &Entry) -> &'static str {
+    match expected.check(e.day, e.part, e.answer) {
+        Verdict::Pass => "PASS",
+        Verdict::Fail { .. } => "FAIL",
+        Verdict::Unknown => "UNKNOWN",
+    }
+}
+
+/// Writes the answered parts as a JSON array, including a `status` field for each when
+/// `expected` is given.
+pub fn write_json(
+    mut w: impl Write,
+    reports: &[Report],
+    expected: Option<&Expected>,
+) -> io::Result<()> {
+    write!(w, "[")?;
+    for (i, e) in entries(reports).enumerate() {
+        let sep = if i > 0 { "," } else { "" };
+        write!(
+            w,
+            "{sep}\n  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}",
+            e.day,
+            e.part,
+            json_string(e.answer),
+            opt(e.parse_ns, "null"),
+            opt(e.solve_ns, "null"),
+        )?;
+        if let Some(expected) = expected {
+            write!(w, ", \"status\": \"{}\"", status(expected, &e))?;
+        }
+        write!(w, "}}")?;
+    }
+    writeln!(w, "\n]")
+}
+
+/// Writes the answered parts as CSV, including a `status` column when `expected` is given.
+pub fn write_csv(
+    mut w: impl Write,
+    reports: &[Report],
+    expected: Option<&Expected>,
+) -> io::Result<()> {
+    write!(w, "day,part,answer,parse_ns,solve_ns")?;
+    writeln!(w, "{}", if expected.is_some() { ",status" } else { "" })?;
+    for e in entries(reports) {
+        write!(
+            w,
+            "{},{},{},{},{}",
+            e.day,
+            e.part,
+            csv_field(e.answer),
+            opt(e.parse_ns, ""),
+            opt(e.solve_ns, ""),
+        )?;
+        match expected {
+            Some(expected) => writeln!(w, ",{}", status(expected, &e))?,
+            None => writeln!(w)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::Stats;
+
+    fn stats(ns: u64) -> Option<Stats> {
+        Stats::from_samples(&mut [Duration::from_nanos(ns)])
+    }
+
+    #[test]
+    fn test_write() {
+        let reports = [Report {
+            day: 3,
+            answers: [Some("a\"b".to_string()), Some("1,2".to_string())],
+            timings: [stats(10), stats(20), None],
+        }];
+
+        let mut json = vec![];
+        write_json(&mut json, &reports, None).unwrap();
+        assert_eq!(
+            String::from_utf8(json).unwrap(),
+            "[\n  {\"day\": 3, \"part\": 1, \"answer\": \"a\\\"b\", \"parse_ns\": 10, \"solve_ns\": 20},\n  \
+             {\"day\": 3, \"part\": 2, \"answer\": \"1,2\", \"parse_ns\": 10, \"solve_ns\": null}\n]\n"
+        );
+
+        let mut csv = vec![];
+        write_csv(&mut csv, &reports, None).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "day,part,answer,parse_ns,solve_ns\n3,1,\"a\"\"b\",10,20\n3,2,\"1,2\",10,\n"
+        );
+
+        let expected = Expected::parse("3 1 a\"b\n3 2 1,3").unwrap();
+        let mut csv = vec![];
+        write_csv(&mut csv, &reports, Some(&expected)).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "day,part,answer,parse_ns,solve_ns,status\n3,1,\"a\"\"b\",10,20,PASS\n3,2,\"1,2\",10,,FAIL\n"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, fmt, path::Path};
+
+use crate::parse::ParseError;
+
+pub const DEFAULT_ANSWERS_PATH: &str = "answers.txt";
+
+/// Known answers, keyed by `(day, part)`.
+///
+/// The file format is one `DAY PART ANSWER` triple per line, separated by whitespace. Blank
+/// lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Expected {
+    answers: BTreeMap<(u8, u8), String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected } => write!(f, "FAIL (expected {expected})"),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Expected {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut answers = BTreeMap::new();
+        for line in input.lines() {
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = content.split_whitespace();
+            let mut num = |what: &str, max: u8| {
+                fields
+                    .next()
+                    .and_then(|f| f.parse().ok())
+                    .filter(|n| (1..=max).contains(n))
+                    .ok_or_else(|| ParseError::at_substr(input, line, format!("expected {what}")))
+            };
+            let day = num("a day", 25)?;
+            let part = num("a part", 2)?;
+            let answer = match (fields.next(), fields.next()) {
+                (Some(answer), None) => answer,
+                _ => {
+                    return Err(ParseError::at_substr(
+                        input,
+                        line,
+                        "expected a single answer after the part",
+                    ))
+                }
+            };
+            answers.insert((day, part), answer.to_string());
+        }
+
+        Ok(Expected { answers })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        Self::parse(&input).map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let expected = Expected::parse(indoc! {
+            "
+            # day part answer
+            7 1 3749
+
+            7 2 11387
+            "
+        })
+        .unwrap();
+        assert_eq!(expected.check(7, 1, "3749"), Verdict::Pass);
+        assert_eq!(
+            expected.check(7, 2, "11386"),
+            Verdict::Fail {
+                expected: "11387".to_string()
+            }
+        );
+        assert_eq!(expected.check(8, 1, "1"), Verdict::Unknown);
+
+        let e = Expected::parse("1 1 2\n1 3 4\n").unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (2, "expected a part"));
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::input;
+
+pub const TEMPLATE_DIR: &str = "day_xx";
+
+pub fn module_name(day: u8) -> String {
+    format!("day_{day:02}")
+}
+
+/// Creates `src_dir/day_NN/mod.rs` from the `day_xx` template (the registry is generated from
+/// the module directories by build.rs, so no other source needs editing) and an empty input file
+/// in `input_dir` if there isn't one already. The runner skips the day while its input is empty.
+///
+/// Refuses to touch anything if the module already exists. Returns the paths that were created.
+pub fn new_day(src_dir: &Path, input_dir: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {day} is out of range, expected 1..=25"));
+    }
+
+    let module_dir = src_dir.join(module_name(day));
+    if module_dir.exists() {
+        return Err(format!("{} already exists", module_dir.display()));
+    }
+
+    let template_path = src_dir.join(TEMPLATE_DIR).join("mod.rs");
+    let template = fs::read_to_string(&template_path)
+        .map_err(|e| format!("failed to read {}: {e}", template_path.display()))?;
+
+    let mut created = vec![];
+
+    let module_path = module_dir.join("mod.rs");
+    fs::create_dir_all(&module_dir)
+        .and_then(|()| fs::write(&module_path, template.replace("xx", &day.to_string())))
+        .map_err(|e| format!("failed to write {}: {e}", module_path.display()))?;
+    created.push(module_path);
+
+    let input_path = input::day_path(input_dir, day);
+    if !input_path.exists() {
+        fs::create_dir_all(input_dir)
+            .and_then(|()| fs::write(&input_path, ""))
+            .map_err(|e| format!("failed to write {}: {e}", input_path.display()))?;
+        created.push(input_path);
+    }
+
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let (src, inputs) = (root.join("src"), root.join("input"));
+        fs::create_dir_all(src.join(TEMPLATE_DIR)).unwrap();
+        fs::write(
+            src.join(TEMPLATE_DIR).join("mod.rs"),
+            "pub struct Dayxx; // dayxx.txt",
+        )
+        .unwrap();
+
+        let created = new_day(&src, &inputs, 14).unwrap();
+        assert_eq!(
+            created,
+            [src.join("day_14").join("mod.rs"), inputs.join("day14.txt")]
+        );
+        assert_eq!(
+            fs::read_to_string(&created[0]).unwrap(),
+            "pub struct Day14; // day14.txt"
+        );
+
+        // never clobber an existing day
+        fs::write(&created[0], "edited").unwrap();
+        assert!(new_day(&src, &inputs, 14).is_err());
+        assert_eq!(fs::read_to_string(&created[0]).unwrap(), "edited");
+
+        // but keep an existing input
+        fs::write(inputs.join("day15.txt"), "input").unwrap();
+        assert_eq!(
+            new_day(&src, &inputs, 15).unwrap(),
+            [src.join("day_15").join("mod.rs")]
+        );
+
+        assert!(new_day(&src, &inputs, 26).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
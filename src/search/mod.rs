@@ -0,0 +1,366 @@
+//! Graph searches over any `neighbours` function, so nodes can be grid positions, states with a
+//! direction, or anything else that's `Clone + Eq + Hash`.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+
+use crate::grid::{Grid, Pos};
+
+/// The set of nodes a search has already seen.
+pub trait Visited<N> {
+    /// Returns whether `node` was newly inserted.
+    fn insert(&mut self, node: N) -> bool;
+    fn contains(&self, node: &N) -> bool;
+    fn clear(&mut self);
+}
+
+impl<N: Eq + Hash> Visited<N> for FxHashSet<N> {
+    fn insert(&mut self, node: N) -> bool {
+        FxHashSet::insert(self, node)
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        FxHashSet::contains(self, node)
+    }
+
+    fn clear(&mut self) {
+        FxHashSet::clear(self)
+    }
+}
+
+impl<N, V: Visited<N>> Visited<N> for &mut V {
+    fn insert(&mut self, node: N) -> bool {
+        V::insert(self, node)
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        V::contains(self, node)
+    }
+
+    fn clear(&mut self) {
+        V::clear(self)
+    }
+}
+
+/// A visited set for nodes that map onto `0..len`, which is much faster than hashing when the
+/// nodes are dense (e.g. grid positions).
+pub struct Bitmap<F> {
+    bits: Vec<u64>,
+    index: F,
+}
+
+impl<F> Bitmap<F> {
+    pub fn new(len: usize, index: F) -> Self {
+        Bitmap {
+            bits: vec![0; len.div_ceil(64)],
+            index,
+        }
+    }
+}
+
+/// A bitmap over the positions of `grid`.
+pub fn grid_bitmap<T>(grid: &Grid<T>) -> Bitmap<impl Fn(&Pos) -> usize> {
+    let width = grid.width();
+    Bitmap::new(grid.width() * grid.height(), move |pos: &Pos| {
+        pos.row * width + pos.col
+    })
+}
+
+impl<N, F: Fn(&N) -> usize> Visited<N> for Bitmap<F> {
+    fn insert(&mut self, node: N) -> bool {
+        let i = (self.index)(&node);
+        let (word, bit) = (&mut self.bits[i / 64], 1 << (i % 64));
+        let new = *word & bit == 0;
+        *word |= bit;
+        new
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        let i = (self.index)(node);
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    fn clear(&mut self) {
+        self.bits.fill(0);
+    }
+}
+
+/// Yields each node reachable from the starts along with its distance, nearest first.
+pub struct Bfs<N, F, V> {
+    queue: VecDeque<(N, usize)>,
+    neighbours: F,
+    visited: V,
+}
+
+pub fn bfs<N, I, F, V>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: F,
+    mut visited: V,
+) -> Bfs<N, F, V>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    V: Visited<N>,
+{
+    let queue = starts
+        .into_iter()
+        .filter(|n| visited.insert(n.clone()))
+        .map(|n| (n, 0))
+        .collect();
+    Bfs {
+        queue,
+        neighbours,
+        visited,
+    }
+}
+
+impl<N, I, F, V> Iterator for Bfs<N, F, V>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    V: Visited<N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, dist) = self.queue.pop_front()?;
+        for next in (self.neighbours)(&node) {
+            if self.visited.insert(next.clone()) {
+                self.queue.push_back((next, dist + 1));
+            }
+        }
+        Some((node, dist))
+    }
+}
+
+/// Yields each node reachable from `start` in depth-first order, using an explicit stack.
+pub struct Dfs<N, F, V> {
+    stack: Vec<N>,
+    neighbours: F,
+    visited: V,
+}
+
+pub fn dfs<N, I, F, V>(start: N, neighbours: F, mut visited: V) -> Dfs<N, F, V>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    V: Visited<N>,
+{
+    visited.insert(start.clone());
+    Dfs {
+        stack: vec![start],
+        neighbours,
+        visited,
+    }
+}
+
+impl<N, I, F, V> Iterator for Dfs<N, F, V>
+where
+    N: Clone,
+    I: IntoIterator<Item = N>,
+    F: FnMut(&N) -> I,
+    V: Visited<N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<N> {
+        let node = self.stack.pop()?;
+        for next in (self.neighbours)(&node) {
+            if self.visited.insert(next.clone()) {
+                self.stack.push(next);
+            }
+        }
+        Some(node)
+    }
+}
+
+/// The shortest path (by number of steps) from `start` to a goal, including both ends.
+pub fn shortest_path<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = FxHashMap::default();
+    let mut queue = VecDeque::from([start.clone()]);
+    let mut visited = FxHashSet::from_iter([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// The cheapest path from `start` to a goal and its cost. `neighbours` gives each neighbour
+/// along with the (non-negative) cost of stepping to it.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but guided by `heuristic`, which must never overestimate the remaining
+/// cost to a goal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut parents = FxHashMap::default();
+    let mut costs = FxHashMap::from_iter([(start.clone(), C::default())]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if costs.get(&node).is_some_and(|&best| best < cost) {
+            // a stale entry, the node was since reached more cheaply
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+fn reconstruct_path<N: Clone + Eq + Hash>(parents: &FxHashMap<N, N>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// The number of distinct paths from `start` to any goal. The graph must be acyclic, and paths
+/// stop at the first goal they reach.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut counts = FxHashMap::default();
+    // `(node, expanded)`, a node's count is summed once all of its neighbours have theirs
+    let mut stack = vec![(start.clone(), false)];
+    while let Some((node, expanded)) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+        if is_goal(&node) {
+            counts.insert(node, 1);
+        } else if expanded {
+            let count = neighbours(&node).into_iter().map(|n| counts[&n]).sum();
+            counts.insert(node, count);
+        } else {
+            stack.push((node.clone(), true));
+            stack.extend(
+                neighbours(&node)
+                    .into_iter()
+                    .filter(|n| !counts.contains_key(n))
+                    .map(|n| (n, false)),
+            );
+        }
+    }
+    counts[&start]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> ... with a shortcut from every multiple of 3 to the number 3 higher.
+    fn neighbours(&n: &u32) -> impl Iterator<Item = u32> {
+        [n + 1, n + 3]
+            .into_iter()
+            .filter(move |&m| m <= 10 && (m == n + 1 || n % 3 == 0))
+    }
+
+    #[test]
+    fn test_traversals() {
+        let bfs_order: Vec<_> = bfs([0], neighbours, FxHashSet::default()).collect();
+        assert_eq!(&bfs_order[..4], [(0, 0), (1, 1), (3, 1), (2, 2)]);
+        assert_eq!(bfs_order.len(), 11);
+
+        let dfs_order: Vec<_> =
+            dfs(0, neighbours, Bitmap::new(11, |&n: &u32| n as usize)).collect();
+        assert_eq!(&dfs_order[..3], [0, 3, 6]);
+        assert_eq!(dfs_order.len(), 11);
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(
+            shortest_path(0, neighbours, |&n| n == 7),
+            Some(vec![0, 3, 6, 7])
+        );
+        assert_eq!(shortest_path(0, neighbours, |&n| n == 11), None);
+
+        // shortcuts are expensive
+        let weighted = |&n: &u32| neighbours(&n).map(move |m| (m, if m == n + 3 { 4 } else { 1 }));
+        assert_eq!(
+            dijkstra(0, weighted, |&n| n == 7),
+            Some((vec![0, 1, 2, 3, 4, 5, 6, 7], 7))
+        );
+        assert_eq!(
+            astar(0, weighted, |&n| 7u32.saturating_sub(n), |&n| n == 7).map(|(_, c)| c),
+            Some(7)
+        );
+
+        // each of 0, 3 and 6 can step by 1 or by 3
+        assert_eq!(count_paths(0, neighbours, |&n| n == 9), 8);
+    }
+
+    #[test]
+    fn test_grid_bitmap() {
+        let grid = Grid::parse("..#\n...\n").unwrap();
+        let reachable = bfs(
+            [Pos::new(0, 0)],
+            |&p| grid.neighbours4(p).filter(|&q| grid[q] == b'.'),
+            grid_bitmap(&grid),
+        );
+        assert_eq!(reachable.map(|(_, dist)| dist).max(), Some(3));
+    }
+}